@@ -0,0 +1,139 @@
+// Benchmarks for the CA core. The width of the words that store the cells is selected with the
+// unit_width cfg, so compare widths by running, for example:
+//
+//   cargo bench --bench ca
+//   RUSTFLAGS='--cfg unit_width="32"' cargo bench --bench ca
+//   RUSTFLAGS='--cfg unit_width="128"' cargo bench --bench ca
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use ga_of_life::ca::{BatchRunner, BitCounter, GameOfLife, GameOfLifeRunner};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+const GARDEN_SIZES: [usize; 2] = [64, 512];
+
+// Returns a CA whose cells are randomly alive with the given probability
+fn random_gol(size: usize, wrap_border: bool, density: f64) -> GameOfLife {
+    let mut rng = StdRng::seed_from_u64(size as u64);
+    let mut gol = GameOfLife::new(size, size, wrap_border);
+
+    for y in 0..size {
+        for x in 0..size {
+            if rng.gen_bool(density) {
+                gol.set(x, y);
+            }
+        }
+    }
+
+    gol
+}
+
+// Returns the live cells of a random 8x8 seed in the center of the garden, similar to those
+// evaluated by the GA
+fn seed_cells(size: usize) -> Vec<(usize, usize)> {
+    random_seed_cells(size, size as u64)
+}
+
+fn random_seed_cells(size: usize, seed: u64) -> Vec<(usize, usize)> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let x0 = (size - 8) / 2;
+
+    (x0..x0 + 8).flat_map(
+        |y| (x0..x0 + 8).map(move |x| (x, y))
+    ).filter(|_| rng.gen_bool(0.5)).collect()
+}
+
+fn bench_step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("step");
+
+    for &size in GARDEN_SIZES.iter() {
+        for &wrap_border in [false, true].iter() {
+            let name = if wrap_border { "wrapped" } else { "zeroes" };
+            let mut gol = random_gol(size, wrap_border, 0.3);
+
+            group.bench_with_input(BenchmarkId::new(name, size), &size, |b, _| {
+                b.iter(|| gol.step())
+            });
+        }
+
+        // A single glider, which wraps around so that it never disappears
+        let mut gol = GameOfLife::new(size, size, true);
+        for &(x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)].iter() {
+            gol.set(x, y);
+        }
+        group.bench_with_input(BenchmarkId::new("sparse", size), &size, |b, _| {
+            b.iter(|| gol.step())
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_count_live_cells(c: &mut Criterion) {
+    let mut group = c.benchmark_group("count_live_cells");
+    let bit_counter = BitCounter::new();
+
+    for &size in GARDEN_SIZES.iter() {
+        let gol = random_gol(size, false, 0.3);
+
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, _| {
+            b.iter(|| bit_counter.count_live_cells(black_box(&gol)))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_run(c: &mut Criterion) {
+    let mut group = c.benchmark_group("run");
+    group.sample_size(20);
+
+    for &size in GARDEN_SIZES.iter() {
+        let seed = seed_cells(size);
+        let mut gol = GameOfLife::new(size, size, false);
+        let mut runner = GameOfLifeRunner::new(100, 2.0);
+
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, _| {
+            b.iter(|| {
+                gol.reset();
+                seed.iter().for_each(|&(x, y)| gol.set(x, y));
+                runner.run(&mut gol)
+            })
+        });
+    }
+
+    group.finish();
+}
+
+// Runs a population of seeds, one at a time as well as all at once
+fn bench_batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch");
+    group.sample_size(10);
+
+    for &size in [32, 64].iter() {
+        let seeds: Vec<_> = (0..100).map(|i| random_seed_cells(size, i)).collect();
+        let new_gardens = || -> Vec<GameOfLife> {
+            seeds.iter().map(|cells| {
+                let mut gol = GameOfLife::new(size, size, false);
+                cells.iter().for_each(|&(x, y)| gol.set(x, y));
+                gol
+            }).collect()
+        };
+
+        let mut runner = GameOfLifeRunner::new(100, 2.0);
+        group.bench_with_input(BenchmarkId::new("single", size), &size, |b, _| {
+            b.iter(|| {
+                new_gardens().iter_mut().map(|gol| runner.run(gol)).collect::<Vec<_>>()
+            })
+        });
+
+        let mut batch_runner = BatchRunner::new(100, 2.0);
+        group.bench_with_input(BenchmarkId::new("packed", size), &size, |b, _| {
+            b.iter(|| batch_runner.run(&mut new_gardens()))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_step, bench_count_live_cells, bench_run, bench_batch);
+criterion_main!(benches);
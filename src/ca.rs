@@ -1,13 +1,43 @@
 use std::{clone, fmt};
-use std::hash::{Hash};
+use std::collections::VecDeque;
 use core::cmp::max;
 use wasm_bindgen::prelude::*;
-
+use serde::{Deserialize, Serialize};
+use escape::EscapeDetector;
+
+pub use batch::BatchRunner;
+pub use census::{Census, CensusTaker};
+pub use hashlife::HashLife;
+pub use immigration::{Immigration, Player};
+pub use patterns::{PatternKind, Transform};
+pub use rule::Rule;
+
+// The words that store the cells. Their width is selected at compile time, as the structs that
+// contain them are exported to WASM, which does not support generic types. Wider words handle more
+// cells per bitwise operation, but increase the padding needed for each row. WASM only has native
+// 32-bit and 64-bit integers, and uses 32-bit words by default, other targets 64-bit words. The
+// default can be overridden by building with, for example, RUSTFLAGS='--cfg unit_width="128"'.
+#[cfg(unit_width = "32")]
+type UnitType = u32;
+#[cfg(unit_width = "64")]
+type UnitType = u64;
+#[cfg(unit_width = "128")]
+type UnitType = u128;
+#[cfg(all(
+    not(any(unit_width = "32", unit_width = "64", unit_width = "128")),
+    target_arch = "wasm32"
+))]
+type UnitType = u32;
+#[cfg(all(
+    not(any(unit_width = "32", unit_width = "64", unit_width = "128")),
+    not(target_arch = "wasm32")
+))]
 type UnitType = u64;
-const BITS_PER_UNIT: usize = 64;
+
+const BITS_PER_UNIT: usize = UnitType::BITS as usize;
 
 #[wasm_bindgen]
-#[derive(clone::Clone, Debug, Hash, Eq, PartialEq)]
+#[derive(clone::Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct BitGrid {
     width: usize,
     height: usize,
@@ -15,16 +45,36 @@ pub struct BitGrid {
     units: Vec<UnitType>
 }
 
-pub struct BitCounter {
-    lookup: Vec<u8>
-}
+pub struct BitCounter {}
 
 const BITS_PER_UNIT_GOL: usize = BITS_PER_UNIT - 1;
 
-#[derive(PartialEq)]
+const DEFAULT_MAX_PERIOD: u32 = 64;
+
+// Thresholds above which GameOfLife::advance switches to HashLife. It is used for runs of at least
+// HASHLIFE_MIN_STEPS steps, as well as for grids of at least HASHLIFE_MIN_CELLS cells when they
+// run for at least HASHLIFE_MIN_STEPS_LARGE_GRID steps.
+const HASHLIFE_MIN_STEPS: u32 = 1024;
+const HASHLIFE_MIN_CELLS: usize = 256 * 256;
+const HASHLIFE_MIN_STEPS_LARGE_GRID: u32 = 64;
+
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum GridBorder {
     Zeroes,
-    Wrapped
+    // A torus: opposite edges are connected
+    Wrapped,
+    // A border of zeroes, where spaceships that are about to reach the border are removed instead
+    // of turning into debris. This is done by GameOfLifeRunner, which counts the escapes.
+    Absorbing,
+    // Like Wrapped, but the top and bottom edges are connected with a horizontal flip
+    KleinBottle,
+    // Like Wrapped, but both pairs of opposite edges are connected with a flip
+    ProjectivePlane,
+    // Each border cell mirrors the state of the adjacent cell in the grid
+    Mirror,
+    // All border cells are permanently alive
+    Alive,
 }
 
 #[wasm_bindgen]
@@ -33,9 +83,15 @@ pub struct GameOfLife {
     width: usize,
     height: usize,
     border: GridBorder,
+    rule: Rule,
     units_per_row: usize,
     num_steps: u32,
     rows: [Vec<UnitType>; 3],
+
+    // For each row of the bit grid, if it contains any live cells. It is used to skip the rows
+    // that cannot change.
+    active_rows: Vec<bool>,
+    skip_inactive: bool,
 }
 
 #[wasm_bindgen]
@@ -50,11 +106,38 @@ pub struct GameOfLifeRunner {
     // run until time t >= T * (1 + min_relative_dormancy)
     min_relative_dormancy: f32,
 
+    // The maximum period of the cycles that are detected. As soon as the CA returns to a state it
+    // was in before, the run is terminated. Zero disables cycle detection.
+    max_period: u32,
+
     bit_counter: BitCounter,
+    escape_detector: EscapeDetector,
+
+    // The most recent states of the CA, used to detect cycles
+    history: VecDeque<RecordedState>,
+    // Buffer of an expired history entry, so that it can be reused
+    spare_state: Vec<UnitType>,
+
+    // The cells that have been alive during the current run, and how many there are. The
+    // allocation is reused by subsequent runs on grids of the same size.
+    toggled: BitGrid,
+    num_toggled: usize,
+
+    // The step at which the current run ends, unless the CA changes before then
+    step_limit: u32,
+}
+
+// A state of the CA, as recorded for cycle detection
+struct RecordedState {
+    step: u32,
+    hash: u64,
+    // A hash that does not change when the state is translated
+    signature: u64,
+    units: Vec<UnitType>,
 }
 
 #[wasm_bindgen]
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct RunStats {
     // The number of cells initially alive
     pub ini_cells: u16,
@@ -75,7 +158,52 @@ pub struct RunStats {
     pub min_cells_after_max_steps: u32,
 
     // The total number of steps executed
-    pub num_steps: u32
+    pub num_steps: u32,
+
+    // The period of the cycle that the CA ended up in. It is zero when no cycle was detected
+    pub period: u32,
+    // The first step of this cycle
+    pub cycle_start_step: u32,
+
+    // The period after which the entire pattern re-appeared at a different location, as happens
+    // for a spaceship. It is zero when no such translation was detected
+    pub translation_period: u32,
+    // The displacement of the pattern after each period
+    pub translation_dx: i32,
+    pub translation_dy: i32,
+    // The first step of the translating cycle
+    pub translation_start_step: u32,
+
+    // The number of spaceships that escaped from the grid, by direction of travel. These are only
+    // tracked for GridBorder::Absorbing
+    pub escaped_n: u16,
+    pub escaped_ne: u16,
+    pub escaped_e: u16,
+    pub escaped_se: u16,
+    pub escaped_s: u16,
+    pub escaped_sw: u16,
+    pub escaped_w: u16,
+    pub escaped_nw: u16,
+}
+
+// Formats an error encountered while parsing a pattern. Line and column are zero-based.
+fn parse_error(line: usize, column: usize, msg: &str) -> String {
+    format!("Line {}, column {}: {}", line + 1, column + 1, msg)
+}
+
+// The maximum number of cells of a parsed pattern. This guards against files whose size or
+// coordinates would require more memory than is available.
+const MAX_PATTERN_CELLS: usize = 1 << 28;
+
+// Checks that a pattern of the given size can be parsed
+fn check_pattern_size(width: usize, height: usize) -> Result<(), String> {
+    match width.checked_mul(height) {
+        Some(cells) if cells <= MAX_PATTERN_CELLS => Ok(()),
+        _ => Err(format!(
+            "Pattern of {}x{} cells exceeds the maximum of {} cells",
+            width, height, MAX_PATTERN_CELLS
+        )),
+    }
 }
 
 impl BitGrid {
@@ -88,7 +216,7 @@ impl BitGrid {
             width,
             height,
             units_per_row,
-            units: vec![0; (height * units_per_row) as usize]
+            units: vec![0; height * units_per_row]
         }
     }
 
@@ -112,14 +240,14 @@ impl BitGrid {
         let index = self.unit_index(x, y);
         let bitpos = x % BITS_PER_UNIT;
 
-        self.units[index] = self.units[index] & !(1 << bitpos);
+        self.units[index] &= !(1 << bitpos);
     }
 
     pub fn set(&mut self, x: usize, y: usize) {
         let index = self.unit_index(x, y);
         let bitpos = x % BITS_PER_UNIT;
 
-        self.units[index] = self.units[index] | (1 << bitpos);
+        self.units[index] |= 1 << bitpos;
     }
 
     pub fn reset(&mut self) {
@@ -130,13 +258,193 @@ impl BitGrid {
         self.units.iter_mut().for_each(|x| *x = !*x);
     }
 
+    // Returns the bits of the given row, starting at the given column. Bit N of the result holds
+    // the cell at column start + N. Columns outside the grid are returned as zeroes, except for
+    // those in the padding of the last unit of the row.
+    pub(crate) fn row_bits(&self, y: usize, start: isize) -> UnitType {
+        let row = &self.units[self.units_per_row * y..self.units_per_row * (y + 1)];
+        let unit = |index: isize| -> UnitType {
+            if index >= 0 && (index as usize) < row.len() { row[index as usize] } else { 0 }
+        };
+
+        let index = start.div_euclid(BITS_PER_UNIT as isize);
+        let offset = start.rem_euclid(BITS_PER_UNIT as isize) as usize;
+        if offset == 0 {
+            unit(index)
+        } else {
+            unit(index) >> offset | unit(index + 1) << (BITS_PER_UNIT - offset)
+        }
+    }
+
+    // Returns a mask that selects the columns from..to of the unit that starts at the given column
+    fn column_mask(unit_start: isize, from: isize, to: isize) -> UnitType {
+        let from = (from - unit_start).clamp(0, BITS_PER_UNIT as isize) as usize;
+        let to = (to - unit_start).clamp(0, BITS_PER_UNIT as isize) as usize;
+
+        if from >= to {
+            0
+        } else {
+            (!0 >> (BITS_PER_UNIT - (to - from))) << from
+        }
+    }
+
+    // Combines the other grid, with its top-left corner at the given position, with this grid.
+    // The given operator combines the units of both grids. Cells outside the area covered by the
+    // other grid are not changed. Parts of the other grid that fall outside this grid are ignored.
+    fn combine_at<F>(&mut self, other: &BitGrid, x0: isize, y0: isize, op: F)
+    where
+        F: Fn(UnitType, UnitType) -> UnitType
+    {
+        let x_from = x0.max(0);
+        let x_to = (x0 + other.width as isize).min(self.width as isize);
+        let y_from = y0.max(0);
+        let y_to = (y0 + other.height as isize).min(self.height as isize);
+        if x_from >= x_to {
+            return;
+        }
+
+        let unit_from = x_from as usize / BITS_PER_UNIT;
+        let unit_to = (x_to as usize - 1) / BITS_PER_UNIT;
+        for y in y_from..y_to {
+            let row_start = self.units_per_row * y as usize;
+            for unit_col in unit_from..=unit_to {
+                let unit_start = (unit_col * BITS_PER_UNIT) as isize;
+                let mask = BitGrid::column_mask(unit_start, x_from, x_to);
+                let src = other.row_bits((y - y0) as usize, unit_start - x0);
+
+                let dst = &mut self.units[row_start + unit_col];
+                *dst = (*dst & !mask) | (op(*dst, src) & mask);
+            }
+        }
+    }
+
     pub fn or(&mut self, other: &BitGrid) {
-        if self.width == other.width && self.height == other.height {
-            self.units.iter_mut().zip(other.units.iter()).for_each(|(x, y)| *x = *x | *y);
+        self.or_at(other, 0, 0);
+    }
+
+    pub fn and(&mut self, other: &BitGrid) {
+        self.and_at(other, 0, 0);
+    }
+
+    pub fn xor(&mut self, other: &BitGrid) {
+        self.xor_at(other, 0, 0);
+    }
+
+    pub fn and_not(&mut self, other: &BitGrid) {
+        self.and_not_at(other, 0, 0);
+    }
+
+    // The operations below combine the other grid, placed with its top-left corner at the given
+    // position, with this one. They only affect the area covered by the other grid.
+    pub fn or_at(&mut self, other: &BitGrid, x0: isize, y0: isize) {
+        self.combine_at(other, x0, y0, |dst, src| dst | src);
+    }
+
+    pub fn and_at(&mut self, other: &BitGrid, x0: isize, y0: isize) {
+        self.combine_at(other, x0, y0, |dst, src| dst & src);
+    }
+
+    pub fn xor_at(&mut self, other: &BitGrid, x0: isize, y0: isize) {
+        self.combine_at(other, x0, y0, |dst, src| dst ^ src);
+    }
+
+    pub fn and_not_at(&mut self, other: &BitGrid, x0: isize, y0: isize) {
+        self.combine_at(other, x0, y0, |dst, src| dst & !src);
+    }
+
+    // Copies the other grid, including its dead cells, with its top-left corner at the given
+    // position.
+    pub fn paste_at(&mut self, other: &BitGrid, x0: isize, y0: isize) {
+        self.combine_at(other, x0, y0, |_, src| src);
+    }
+
+    // Moves all cells by the given offset. Cells that move outside the grid are lost.
+    pub fn shift(&mut self, dx: isize, dy: isize) {
+        let original = self.clone();
+
+        self.reset();
+        self.paste_at(&original, dx, dy);
+    }
+
+    // Returns the given rectangle of the grid. Parts of the rectangle outside the grid are empty.
+    pub fn crop(&self, x0: isize, y0: isize, width: usize, height: usize) -> BitGrid {
+        let mut cropped = BitGrid::new(width, height);
+
+        cropped.paste_at(self, -x0, -y0);
+        cropped
+    }
+
+    // Returns the smallest rectangle that contains all live cells as (x, y, width, height), or
+    // None when there are no live cells.
+    pub fn bounding_box(&self) -> Option<(usize, usize, usize, usize)> {
+        let mut x_min = usize::MAX;
+        let mut x_max = 0;
+        let mut y_min = usize::MAX;
+        let mut y_max = 0;
+
+        for y in 0..self.height {
+            for unit_col in 0..self.units_per_row {
+                let mask = BitGrid::column_mask(
+                    (unit_col * BITS_PER_UNIT) as isize, 0, self.width as isize
+                );
+                let unit = self.units[self.units_per_row * y + unit_col] & mask;
+                if unit == 0 {
+                    continue;
+                }
+
+                let unit_start = unit_col * BITS_PER_UNIT;
+                x_min = x_min.min(unit_start + unit.trailing_zeros() as usize);
+                x_max = x_max.max(unit_start + BITS_PER_UNIT - 1 - unit.leading_zeros() as usize);
+                y_min = y_min.min(y);
+                y_max = y;
+            }
+        }
+
+        if y_min == usize::MAX {
+            None
         } else {
-            panic!("Not yet implemented");
+            Some((x_min, y_min, x_max - x_min + 1, y_max - y_min + 1))
         }
     }
+
+    pub fn from_rle_result(rle: &str) -> Result<Self, String> {
+        rle::parse(rle).map(|(bit_grid, _)| bit_grid)
+    }
+
+    pub fn from_plaintext_result(text: &str) -> Result<Self, String> {
+        plaintext::parse(text)
+    }
+
+    pub fn from_life106_result(text: &str) -> Result<Self, String> {
+        life106::parse(text)
+    }
+}
+
+#[wasm_bindgen]
+impl BitGrid {
+    pub fn from_rle(rle: &str) -> Result<BitGrid, JsValue> {
+        BitGrid::from_rle_result(rle).map_err(|msg| JsValue::from_str(&msg))
+    }
+
+    pub fn to_rle(&self) -> String {
+        rle::write(self, None)
+    }
+
+    pub fn from_plaintext(text: &str) -> Result<BitGrid, JsValue> {
+        BitGrid::from_plaintext_result(text).map_err(|msg| JsValue::from_str(&msg))
+    }
+
+    pub fn to_plaintext(&self) -> String {
+        plaintext::write(self)
+    }
+
+    pub fn from_life106(text: &str) -> Result<BitGrid, JsValue> {
+        BitGrid::from_life106_result(text).map_err(|msg| JsValue::from_str(&msg))
+    }
+
+    pub fn to_life106(&self) -> String {
+        life106::write(self)
+    }
 }
 
 impl fmt::Display for BitGrid {
@@ -150,49 +458,22 @@ impl fmt::Display for BitGrid {
                 };
                 write!(f, "{}", symbol)?;
             }
-            write!(f, "\n")?;
+            writeln!(f)?;
         }
 
         Ok(())
     }
 }
 
+// Bits are counted using count_ones, which compiles to a single popcount instruction on targets
+// that support it.
 impl BitCounter {
-    fn count_bits(mut val: u8) -> u8 {
-        let mut count = 0;
-        while val != 0 {
-            if val & 1 == 1 {
-                count += 1;
-            }
-            val >>= 1;
-        }
-        count
-    }
-
     pub fn new() -> Self {
-        let mut lookup = Vec::with_capacity(256);
-        for i in 0..=255 {
-            lookup.push(BitCounter::count_bits(i));
-        }
-
-        BitCounter {
-            lookup
-        }
+        BitCounter {}
     }
 
     pub fn count_set_bits(&self, bit_grid: &BitGrid) -> usize {
-        let mut count: usize = 0;
-        for unit in bit_grid.units.iter() {
-            count += self.lookup[(unit & 255) as usize] as usize;
-            count += self.lookup[((unit >> 8) & 255) as usize] as usize;
-            count += self.lookup[((unit >> 16) & 255) as usize] as usize;
-            count += self.lookup[((unit >> 24) & 255) as usize] as usize;
-            count += self.lookup[((unit >> 32) & 255) as usize] as usize;
-            count += self.lookup[((unit >> 40) & 255) as usize] as usize;
-            count += self.lookup[((unit >> 48) & 255) as usize] as usize;
-            count += self.lookup[((unit >> 56) & 255) as usize] as usize;
-        }
-        count
+        bit_grid.units.iter().map(|unit| unit.count_ones() as usize).sum()
     }
 
     // Counts the number of live cells in the provided bit grid, assuming the grid is storing the
@@ -204,19 +485,7 @@ impl BitCounter {
         assert_eq!(gol.bit_grid.width, bit_grid.width);
         assert_eq!(gol.bit_grid.height, bit_grid.height);
 
-        let mask_c: UnitType = !(1 << BITS_PER_UNIT_GOL);
-        let mask_l = mask_c & !1;
-        let mut mask_r = if gol.units_per_row == 1 {
-            mask_l
-        } else {
-            mask_c
-        };
-
-        // The number of bits in the rightmost unit of each row that are used by the CA.
-        let bits_in_last_unit = gol.width % BITS_PER_UNIT_GOL + 1;
-        if bits_in_last_unit < BITS_PER_UNIT {
-            mask_r &= !0 >> (BITS_PER_UNIT - bits_in_last_unit);
-        }
+        let (mask_l, mask_c, mask_r) = gol.unit_masks();
 
         let mut count: usize = 0;
         let mut i = 0;
@@ -234,15 +503,38 @@ impl BitCounter {
                 mask_c
             };
 
-            let val = *unit & mask;
-            count += self.lookup[(val & 255) as usize] as usize;
-            count += self.lookup[((val >> 8) & 255) as usize] as usize;
-            count += self.lookup[((val >> 16) & 255) as usize] as usize;
-            count += self.lookup[((val >> 24) & 255) as usize] as usize;
-            count += self.lookup[((val >> 32) & 255) as usize] as usize;
-            count += self.lookup[((val >> 40) & 255) as usize] as usize;
-            count += self.lookup[((val >> 48) & 255) as usize] as usize;
-            count += self.lookup[((val >> 56) & 255) as usize] as usize;
+            count += (*unit & mask).count_ones() as usize;
+        }
+        count
+    }
+
+    // Sets all live cells of the Game of Life in the bit grid, which should have the same layout as
+    // the bit grid owned by the former. Returns how many of these cells were not yet set. This way,
+    // the number of cells that were ever alive can be tracked without recounting all of them.
+    fn merge_live_cells(&self, gol: &GameOfLife, bit_grid: &mut BitGrid) -> usize {
+        assert_eq!(gol.bit_grid.width, bit_grid.width);
+        assert_eq!(gol.bit_grid.height, bit_grid.height);
+
+        let (mask_l, mask_c, mask_r) = gol.unit_masks();
+        let units_per_row = gol.units_per_row;
+        let rows = units_per_row..units_per_row * (gol.height + 1);
+
+        let mut count: usize = 0;
+        for (dst_row, src_row) in bit_grid.units[rows.clone()].chunks_exact_mut(units_per_row).zip(
+            gol.bit_grid.units[rows].chunks_exact(units_per_row)
+        ) {
+            for (i, (dst, src)) in dst_row.iter_mut().zip(src_row.iter()).enumerate() {
+                let mask = if i == units_per_row - 1 {
+                    mask_r
+                } else if i == 0 {
+                    mask_l
+                } else {
+                    mask_c
+                };
+
+                count += (*src & !*dst & mask).count_ones() as usize;
+                *dst |= *src;
+            }
         }
         count
     }
@@ -252,6 +544,12 @@ impl BitCounter {
     }
 }
 
+impl Default for BitCounter {
+    fn default() -> Self {
+        BitCounter::new()
+    }
+}
+
 
 // Public implementation for GameOfLife that is excluded from WASM interface
 impl GameOfLife {
@@ -264,7 +562,7 @@ impl GameOfLife {
     //    avoids the need to look the next unit column when updating cells _during_ the update
     //    loop.
     pub fn new_result(width: usize, height: usize, border: GridBorder) -> Result<Self, String> {
-        let units_per_row = (width + 1 + (BITS_PER_UNIT_GOL - 1)) / BITS_PER_UNIT_GOL;
+        let units_per_row = (width + 1).div_ceil(BITS_PER_UNIT_GOL);
 
         if width < 3 || height < 3 {
             return Err("Size too small".to_string());
@@ -275,11 +573,437 @@ impl GameOfLife {
             width,
             height,
             border,
+            rule: Rule::conway(),
             units_per_row,
             num_steps: 0,
-            rows: [vec![0; units_per_row], vec![0; units_per_row], vec![0; units_per_row]]
+            rows: [vec![0; units_per_row], vec![0; units_per_row], vec![0; units_per_row]],
+            active_rows: vec![false; height + 2],
+            skip_inactive: true,
         })
     }
+
+    pub fn rule(&self) -> &Rule {
+        &self.rule
+    }
+
+    // Returns if advancing the given number of steps is done using HashLife. This requires a
+    // border of zeroes, which HashLife emulates by surrounding the grid with cells that are always
+    // dead. It also requires a rule without births on zero neighbours, as HashLife assumes that
+    // empty regions stay empty. Its overhead only pays off for long runs or large grids.
+    fn uses_hashlife(&self, num_steps: u32) -> bool {
+        self.border == GridBorder::Zeroes && !self.rule.births_on(0) && (
+            num_steps >= HASHLIFE_MIN_STEPS || (
+                self.num_cells() >= HASHLIFE_MIN_CELLS &&
+                num_steps >= HASHLIFE_MIN_STEPS_LARGE_GRID
+            )
+        )
+    }
+
+    // Returns the masks for the leftmost, central and rightmost units of each row. These select
+    // the bits that represent the CA cells, excluding border cells and duplicated bits.
+    fn unit_masks(&self) -> (UnitType, UnitType, UnitType) {
+        let mask_c: UnitType = !(1 << BITS_PER_UNIT_GOL);
+        let mask_l = mask_c & !1;
+        let mut mask_r = if self.units_per_row == 1 {
+            mask_l
+        } else {
+            mask_c
+        };
+
+        // The number of bits in the rightmost unit of each row that are used by the CA.
+        let bits_in_last_unit = self.width % BITS_PER_UNIT_GOL + 1;
+        if bits_in_last_unit < BITS_PER_UNIT {
+            mask_r &= !0 >> (BITS_PER_UNIT - bits_in_last_unit);
+        }
+
+        (mask_l, mask_c, mask_r)
+    }
+
+    // Returns if the given cell is alive in a state as obtained by copy_state.
+    fn state_get(&self, state: &[UnitType], x: usize, y: usize) -> bool {
+        let unit = state[(x + 1) / BITS_PER_UNIT_GOL + self.units_per_row * y];
+        ((unit >> ((x + 1) % BITS_PER_UNIT_GOL)) & 1) == 1
+    }
+
+    // Returns the live cells of a state as obtained by copy_state, row by row.
+    fn state_cells(&self, state: &[UnitType]) -> Vec<(usize, usize)> {
+        let mut cells = Vec::new();
+
+        for (y, row) in state.chunks(self.units_per_row).enumerate() {
+            for (unit_col, &unit) in row.iter().enumerate() {
+                // Only visit the bits of live cells. The masks applied by copy_state ensure that
+                // these do not include border bits.
+                let mut bits = unit;
+                while bits != 0 {
+                    let pos = bits.trailing_zeros() as usize;
+                    cells.push((unit_col * BITS_PER_UNIT_GOL + pos - 1, y));
+                    bits &= bits - 1;
+                }
+            }
+        }
+
+        cells
+    }
+
+    // Returns the width and height of the bounding box of the live cells in a state as obtained by
+    // copy_state.
+    fn state_extent(&self, state: &[UnitType]) -> (usize, usize) {
+        let (mut pos_min, mut pos_max) = (usize::MAX, 0);
+        let (mut y_min, mut y_max) = (usize::MAX, 0);
+
+        for (y, row) in state.chunks(self.units_per_row).enumerate() {
+            if let Some(first) = row.iter().position(|&unit| unit != 0) {
+                let last = row.iter().rposition(|&unit| unit != 0).unwrap();
+                let first_pos = row[first].trailing_zeros() as usize;
+                let last_pos = BITS_PER_UNIT - 1 - row[last].leading_zeros() as usize;
+                pos_min = pos_min.min(first * BITS_PER_UNIT_GOL + first_pos);
+                pos_max = pos_max.max(last * BITS_PER_UNIT_GOL + last_pos);
+                y_min = y_min.min(y);
+                y_max = y;
+            }
+        }
+
+        if y_min > y_max { (0, 0) } else { (pos_max - pos_min + 1, y_max - y_min + 1) }
+    }
+
+    // Returns a hash of the state that is invariant under translation. It combines the number of
+    // live cells in each row in an order-independent way. On a projective plane, the rows of a
+    // pattern that crosses the left or right edge are flipped, so only the number of live cells is
+    // used.
+    fn translation_signature(&self, state: &[UnitType]) -> u64 {
+        if self.border == GridBorder::ProjectivePlane {
+            return state.iter().map(|unit| unit.count_ones() as u64).sum();
+        }
+
+        let signature = state.chunks(self.units_per_row).map(|row| {
+            // SplitMix64 finalizer
+            let mut z = row.iter().map(|unit| unit.count_ones() as u64).sum::<u64>();
+            z = z.wrapping_add(0x9E3779B97F4A7C15);
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            z ^ (z >> 31)
+        }).fold(0u64, |acc, z| acc.wrapping_add(z));
+        if self.wraps() {
+            return signature;
+        }
+
+        // Without wrapping, the size of the bounding box is invariant as well. This tells apart
+        // the states of, for example, a spaceship that moves away from static debris.
+        let (width, height) = self.state_extent(state);
+        signature ^ ((width as u64) << 32 | height as u64).wrapping_mul(0x9E3779B97F4A7C15)
+    }
+
+    // Returns true when cells that leave the grid on one side re-enter it on the opposite side
+    fn wraps(&self) -> bool {
+        matches!(
+            self.border,
+            GridBorder::Wrapped | GridBorder::KleinBottle | GridBorder::ProjectivePlane
+        )
+    }
+
+    // Moves a cell by the given displacement. When the cell crosses an edge of a wrapping grid, it
+    // re-enters at the opposite edge, flipped as done by set_border_bits. Returns None when the
+    // cell ends up outside a grid that does not wrap.
+    fn displace(&self, x: usize, y: usize, dx: i64, dy: i64) -> Option<(usize, usize)> {
+        let (w, h) = (self.width as i64, self.height as i64);
+        let (mut x, mut y) = (x as i64 + dx, y as i64 + dy);
+
+        while x < 0 || x >= w || y < 0 || y >= h {
+            let y_outside = y < 0 || y >= h;
+            (x, y) = match self.border {
+                GridBorder::Wrapped => (x.rem_euclid(w), y.rem_euclid(h)),
+                GridBorder::KleinBottle | GridBorder::ProjectivePlane if y_outside => {
+                    (w - 1 - x, y.rem_euclid(h))
+                },
+                GridBorder::KleinBottle => (x.rem_euclid(w), y),
+                GridBorder::ProjectivePlane => (x.rem_euclid(w), h - 1 - y),
+                _ => return None,
+            };
+        }
+
+        Some((x as usize, y as usize))
+    }
+
+    // Returns the displacements that move the first cell onto the second one. Besides the direct
+    // one, these include those across the edges where the cell is flipped.
+    fn displacement_candidates(
+        &self, (x0, y0): (usize, usize), (x1, y1): (usize, usize)
+    ) -> Vec<(i64, i64)> {
+        let (w, h) = (self.width as i64, self.height as i64);
+        let (x0, y0, x1, y1) = (x0 as i64, y0 as i64, x1 as i64, y1 as i64);
+        let mut candidates = vec![(x1 - x0, y1 - y0)];
+
+        if self.border == GridBorder::KleinBottle || self.border == GridBorder::ProjectivePlane {
+            // Across the top or bottom edge, which flips the cell horizontally
+            let dx = w - 1 - x1 - x0;
+            candidates.push((dx, y1 + h - y0));
+            candidates.push((dx, y1 - h - y0));
+        }
+        if self.border == GridBorder::ProjectivePlane {
+            // Across the left or right edge, which flips the cell vertically
+            let dy = h - 1 - y1 - y0;
+            candidates.push((x1 + w - x0, dy));
+            candidates.push((x1 - w - x0, dy));
+        }
+
+        candidates
+    }
+
+    // Returns the displacement that moves all live cells of one state onto those of another state,
+    // if it exists. Both states are obtained by copy_state.
+    fn find_displacement(&self, from: &[UnitType], to: &[UnitType]) -> Option<(i32, i32)> {
+        let from_cells = self.state_cells(from);
+        let to_cells = self.state_cells(to);
+        if from_cells.len() != to_cells.len() || from_cells.is_empty() {
+            return None;
+        }
+
+        // The first cell must map onto one of the cells of the other state. Without wrapping, a
+        // translation preserves the order of the cells, so it can only map onto the first one.
+        let num_targets = if self.wraps() { to_cells.len() } else { 1 };
+        let (x0, y0) = from_cells[0];
+        let candidates = to_cells[..num_targets].iter().flat_map(
+            |&to_cell| self.displacement_candidates((x0, y0), to_cell)
+        );
+        for (dx, dy) in candidates {
+            if from_cells.iter().all(|&(x, y)| match self.displace(x, y, dx, dy) {
+                Some((x, y)) => self.state_get(to, x, y),
+                None => false
+            }) {
+                // Report the smallest displacement along the axes that wrap without a flip
+                let shortest = |d: i64, size: i64| {
+                    let d = d.rem_euclid(size);
+                    if d > size / 2 { d - size } else { d }
+                };
+                let (w, h) = (self.width as i64, self.height as i64);
+                return Some(match self.border {
+                    GridBorder::Wrapped => (shortest(dx, w) as i32, shortest(dy, h) as i32),
+                    GridBorder::KleinBottle => (shortest(dx, w) as i32, dy as i32),
+                    _ => (dx as i32, dy as i32),
+                });
+            }
+        }
+
+        None
+    }
+
+    // Copies the state of all cells to the given buffer. Bits that do not represent cells are
+    // cleared, so that states can be compared.
+    fn copy_state(&self, state: &mut Vec<UnitType>) {
+        let (mask_l, mask_c, mask_r) = self.unit_masks();
+
+        state.clear();
+        for row in self.bit_grid.units[
+            self.units_per_row..self.units_per_row * (self.height + 1)
+        ].chunks(self.units_per_row) {
+            for (i, unit) in row.iter().enumerate() {
+                let mask = if i == self.units_per_row - 1 {
+                    mask_r
+                } else if i == 0 {
+                    mask_l
+                } else {
+                    mask_c
+                };
+                state.push(*unit & mask);
+            }
+        }
+    }
+
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+    }
+
+    // Returns a copy of the current state of the CA, without any border cells.
+    pub fn to_bit_grid(&self) -> BitGrid {
+        let mut bit_grid = BitGrid::new(self.width, self.height);
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.get(x, y) {
+                    bit_grid.set(x, y);
+                }
+            }
+        }
+
+        bit_grid
+    }
+
+    // Sets the cells that are alive in the given bit grid, with its top-left corner at the given
+    // position. Other cells are not changed.
+    pub fn paste(&mut self, bit_grid: &BitGrid, x0: usize, y0: usize) -> Result<(), String> {
+        if x0 + bit_grid.width() > self.width || y0 + bit_grid.height() > self.height {
+            return Err("Pattern does not fit".to_string());
+        }
+
+        // Bit N of each unit of the CA represents the cell at column (index * BITS_PER_UNIT_GOL +
+        // N - 1), where index is the position of the unit in its row.
+        let x_from = x0 as isize;
+        let x_to = (x0 + bit_grid.width()) as isize;
+        for y in 0..bit_grid.height() {
+            let row_start = self.units_per_row * (y0 + y + 1);
+            for unit_col in 0..self.units_per_row {
+                let unit_start = (unit_col * BITS_PER_UNIT_GOL) as isize - 1;
+                let mask = BitGrid::column_mask(unit_start, x_from, x_to)
+                    & !(1 << BITS_PER_UNIT_GOL);
+                if mask != 0 {
+                    let src = bit_grid.row_bits(y, unit_start - x_from);
+                    self.bit_grid.units[row_start + unit_col] |= src & mask;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Places the pattern from the catalog with the given name, after applying the transform, with
+    // its top-left corner at the given position.
+    pub fn place_pattern_result(
+        &mut self, name: &str, x0: usize, y0: usize, transform: Transform
+    ) -> Result<(), String> {
+        let pattern = patterns::find(name).ok_or(format!("Unknown pattern \"{}\"", name))?;
+
+        self.paste(&pattern.to_bit_grid(transform), x0, y0)
+    }
+
+    // Replaces the contents of the CA by the given RLE pattern, which is centered in the grid. The
+    // rule is changed as well when the pattern specifies it.
+    pub fn load_rle_result(&mut self, rle: &str) -> Result<(), String> {
+        let (bit_grid, rule) = rle::parse(rle)?;
+        if bit_grid.width() > self.width || bit_grid.height() > self.height {
+            return Err("Pattern does not fit".to_string());
+        }
+
+        self.reset();
+        if let Some(rule) = rule {
+            self.rule = rule;
+        }
+
+        self.paste(
+            &bit_grid,
+            (self.width - bit_grid.width()) / 2,
+            (self.height - bit_grid.height()) / 2
+        )
+    }
+
+    // Performs a single step for an arbitrary Life-like rule.
+    fn step_generic(&mut self) {
+        let rule = self.rule;
+        self.step_with(|currn, sum0, car0, sum1, car1| {
+            // Convert the outcome of the adder network to a four-bit binary neighbour count
+            let car01 = car0 & sum1;
+            rule.apply(currn, [sum0, car0 ^ sum1, car1 ^ car01, car1 & car01])
+        });
+    }
+
+    fn find_active_rows(&mut self) {
+        for (active, row) in self.active_rows.iter_mut().zip(
+            self.bit_grid.units.chunks_exact(self.units_per_row)
+        ) {
+            *active = row.iter().any(|unit| *unit != 0);
+        }
+    }
+
+    // Performs a single step. The provided function determines the new state of the cells in a
+    // unit. Next to the current state, it is given the outcome of the adder network that counts the
+    // live neighbours of each cell. The neighbour count equals sum0 + 2 * (car0 + sum1) + 4 * car1.
+    fn step_with<F>(&mut self, next_state: F)
+    where
+        F: Fn(UnitType, UnitType, UnitType, UnitType, UnitType) -> UnitType
+    {
+        let mut row_above = 0;
+        let mut row_currn = 1;
+        let mut row_below = 2;
+
+        self.num_steps += 1;
+
+        self.restore_right_bits();
+        self.set_border_bits();
+
+        // Dead cells without live neighbours remain dead, unless the rule has birth on zero
+        // neighbours. Rows and units where this applies to all cells are therefore skipped. This
+        // does not affect the outcome, as these would be computed to remain zero.
+        let skip_inactive = self.skip_inactive && next_state(0, 0, 0, 0, 0) == 0;
+        if skip_inactive {
+            self.find_active_rows();
+        }
+
+        let mut rows_loaded = false;
+        for row in 1..self.bit_grid.height - 1 {
+            if skip_inactive
+                && !(self.active_rows[row - 1] || self.active_rows[row] || self.active_rows[row + 1])
+            {
+                rows_loaded = false;
+                continue;
+            }
+
+            if !rows_loaded {
+                // Init row above and current row. The row above is the top border row or it was
+                // skipped, so it still contains its original state.
+                self.rows[row_above][0..self.units_per_row].copy_from_slice(
+                    &self.bit_grid.units[self.units_per_row * (row - 1)..self.units_per_row * row]
+                );
+                self.rows[row_currn][0..self.units_per_row].copy_from_slice(
+                    &self.bit_grid.units[self.units_per_row * row..self.units_per_row * (row + 1)]
+                );
+                rows_loaded = true;
+            }
+
+            // Init row below
+            self.rows[row_below][0..self.units_per_row].copy_from_slice(
+                &self.bit_grid.units[self.units_per_row * (row + 1)..self.units_per_row * (row + 2)]
+            );
+
+            // State needed for neighbours at the left (for leftmost cells in current unit column)
+            let mut abc_sum_prev = 0;
+            let mut abc_car_prev = 0;
+
+            let mut unit_index = self.units_per_row * row;
+            for col in 0..self.units_per_row {
+                let above = self.rows[row_above][col];
+                let below = self.rows[row_below][col];
+                let currn = self.rows[row_currn][col];
+
+                if skip_inactive && (above | below | currn | abc_sum_prev | abc_car_prev) == 0 {
+                    unit_index += 1;
+                    continue;
+                }
+
+                // above + below
+                let ab_sum = above ^ below;
+                let ab_car = above & below;
+
+                // above + below + current
+                let abc_sum = currn ^ ab_sum;
+                let abc_car = currn & ab_sum | ab_car;
+
+                // sum of bit0 (sum of sums)
+                let l = abc_sum << 1 | abc_sum_prev >> (BITS_PER_UNIT_GOL - 1);
+                let r = abc_sum >> 1; // Note: cannot include abc_sum_next, so incorrect for
+                                      // rightmost bit.
+                let lr = l ^ r;
+                let sum0 = lr ^ ab_sum;
+                let car0 = l & r | lr & ab_sum;
+
+                // sum of bit1 (sum of carry's)
+                let l = abc_car << 1 | abc_car_prev >> (BITS_PER_UNIT_GOL - 1);
+                let r = abc_car >> 1;
+                let lr = l ^ r;
+                let sum1 = lr ^ ab_car;
+                let car1 = l & r | lr & ab_car;
+
+                self.bit_grid.units[unit_index] = next_state(currn, sum0, car0, sum1, car1);
+                unit_index += 1;
+
+                abc_sum_prev = abc_sum;
+                abc_car_prev = abc_car;
+            }
+
+            let row_tmp = row_above;
+            row_above = row_currn;
+            row_currn = row_below;
+            row_below = row_tmp;
+        }
+    }
 }
 
 #[wasm_bindgen]
@@ -304,6 +1028,10 @@ impl GameOfLife {
         ).unwrap()
     }
 
+    pub fn with_border(width: usize, height: usize, border: GridBorder) -> Self {
+        GameOfLife::new_result(width, height, border).unwrap()
+    }
+
     pub fn width(&self) -> usize {
         self.width
     }
@@ -379,9 +1107,85 @@ impl GameOfLife {
     }
 
     fn set_border_bits(&mut self) {
-        match &self.border {
-            GridBorder::Zeroes => self.set_zeroes_border(),
-            GridBorder::Wrapped => self.set_wrapped_border()
+        let (w, h) = (self.width as isize, self.height as isize);
+        let flip_x = |x: isize| w - 1 - x;
+        let flip_y = |y: isize| h - 1 - y;
+
+        match self.border {
+            GridBorder::Zeroes | GridBorder::Absorbing => self.set_zeroes_border(),
+            GridBorder::Wrapped => self.set_wrapped_border(),
+            GridBorder::KleinBottle => self.set_border_from(|x, y| {
+                if y < 0 || y >= h { (flip_x(x), y.rem_euclid(h)) } else { (x.rem_euclid(w), y) }
+            }),
+            GridBorder::ProjectivePlane => self.set_border_from(|x, y| {
+                if y < 0 || y >= h {
+                    (flip_x(x), y.rem_euclid(h))
+                } else {
+                    (x.rem_euclid(w), flip_y(y))
+                }
+            }),
+            GridBorder::Mirror => self.set_border_from(
+                |x, y| (x.clamp(0, w - 1), y.clamp(0, h - 1))
+            ),
+            GridBorder::Alive => self.set_border_with(|_, _, _| true),
+        }
+    }
+
+    // Sets each border cell to the state of a cell in the grid. The given function maps the
+    // position of a cell outside the grid to another position. It is applied repeatedly, until it
+    // yields a position inside the grid.
+    fn set_border_from<F>(&mut self, source: F)
+    where
+        F: Fn(isize, isize) -> (isize, isize)
+    {
+        let (w, h) = (self.width as isize, self.height as isize);
+
+        self.set_border_with(|gol, mut x, mut y| {
+            while x < 0 || x >= w || y < 0 || y >= h {
+                let pos = source(x, y);
+                x = pos.0;
+                y = pos.1;
+            }
+            gol.get(x as usize, y as usize)
+        });
+    }
+
+    // Sets each border cell to the state returned by the given function for its position. Unlike
+    // set_zeroes_border and set_wrapped_border, this sets each cell individually.
+    fn set_border_with<F>(&mut self, alive: F)
+    where
+        F: Fn(&GameOfLife, isize, isize) -> bool
+    {
+        let (w, h) = (self.width as isize, self.height as isize);
+
+        for y in -1..=h {
+            let step = if y < 0 || y == h { 1 } else { w + 1 };
+            for x in (-1..=w).step_by(step as usize) {
+                let value = alive(self, x, y);
+                self.set_border_cell(x, y, value);
+            }
+        }
+    }
+
+    fn set_border_cell(&mut self, x: isize, y: isize, alive: bool) {
+        let pos = (x + 1) as usize;
+        let row_start = self.units_per_row * (y + 1) as usize;
+        let units = &mut self.bit_grid.units;
+        let mut set_bit = |index: usize, bitpos: usize| {
+            if alive {
+                units[index] |= 1 << bitpos;
+            } else {
+                units[index] &= !(1 << bitpos);
+            }
+        };
+
+        if pos < self.units_per_row * BITS_PER_UNIT_GOL {
+            set_bit(row_start + pos / BITS_PER_UNIT_GOL, pos % BITS_PER_UNIT_GOL);
+        }
+        // The first bit of each unit is duplicated by the last bit of the previous unit. When the
+        // right border is at the first bit of a new unit, it is only represented by the latter.
+        if pos > 0 && pos.is_multiple_of(BITS_PER_UNIT_GOL) {
+            set_bit(row_start + pos / BITS_PER_UNIT_GOL - 1, BITS_PER_UNIT_GOL);
         }
     }
 
@@ -401,75 +1205,70 @@ impl GameOfLife {
     // The Game of Life rules are implemented by performing bitwise calculations. This is based on
     // the Pico-8 implementation by rilden at: https://www.lexaloffle.com/bbs/?pid=94115
     pub fn step(&mut self) {
-        let mut row_above = 0;
-        let mut row_currn = 1;
-        let mut row_below = 2;
-
-        self.num_steps += 1;
-
-        self.restore_right_bits();
-        self.set_border_bits();
-
-        // Init row above to Row #0 of grid
-        self.rows[row_above][0..self.units_per_row].copy_from_slice(
-            &self.bit_grid.units[0..self.units_per_row]
-        );
-        // Init current row to Row #1 of grid
-        self.rows[row_currn][0..self.units_per_row].copy_from_slice(
-            &self.bit_grid.units[self.units_per_row..self.units_per_row * 2]
-        );
-
-        let mut unit_index = self.units_per_row;
-        for row in 1..self.bit_grid.height - 1 {
-            // Init row below
-            self.rows[row_below][0..self.units_per_row].copy_from_slice(
-                &self.bit_grid.units[self.units_per_row * (row + 1)..self.units_per_row * (row + 2)]
-            );
-
-            // State needed for neighbours at the left (for leftmost cells in current unit column)
-            let mut abc_sum_prev = 0;
-            let mut abc_car_prev = 0;
+        if self.rule.is_conway() {
+            // Fast path for B3/S23
+            self.step_with(|currn, sum0, car0, sum1, car1| (currn | sum0) & (car0 ^ sum1) & !car1);
+        } else {
+            self.step_generic();
+        }
+    }
 
-            for col in 0..self.units_per_row {
-                let above = self.rows[row_above][col];
-                let below = self.rows[row_below][col];
-                let currn = self.rows[row_currn][col];
+    // Advances the given number of steps. Depending on the size of the grid and the number of
+    // steps, this is done by repeatedly invoking step() or using HashLife. Both give identical
+    // results. Each call builds a new HashLife, so memoised results are not reused by subsequent
+    // calls. To advance repeatedly, keep a HashLife and use advance_with instead.
+    pub fn advance(&mut self, num_steps: u32) {
+        if self.uses_hashlife(num_steps) {
+            let mut hashlife = HashLife::from_game_of_life(self);
+            self.advance_with(&mut hashlife, num_steps);
+        } else {
+            for _ in 0..num_steps {
+                self.step();
+            }
+        }
+    }
 
-                // above + below
-                let ab_sum = above ^ below;
-                let ab_car = above & below;
+    // Advances the given number of steps using the HashLife, which should hold the current state
+    // of this CA, and copies the result back. The HashLife can be reused by the next call.
+    fn advance_with(&mut self, hashlife: &mut HashLife, num_steps: u32) {
+        hashlife.step(num_steps as u64);
+        hashlife.write_to(self);
+        self.num_steps += num_steps;
+    }
 
-                // above + below + current
-                let abc_sum = currn ^ ab_sum;
-                let abc_car = currn & ab_sum | ab_car;
+    pub fn rulestring(&self) -> String {
+        format!("{}", self.rule)
+    }
 
-                // sum of bit0 (sum of sums)
-                let l = abc_sum << 1 | abc_sum_prev >> (BITS_PER_UNIT_GOL - 1);
-                let r = abc_sum >> 1; // Note: cannot include abc_sum_next, so incorrect for
-                                      // rightmost bit.
-                let lr = l ^ r;
-                let sum0 = lr ^ ab_sum;
-                let car0 = l & r | lr & ab_sum;
+    pub fn set_rulestring(&mut self, rulestring: &str) -> Result<(), JsValue> {
+        self.rule = rulestring.parse().map_err(|msg: String| JsValue::from_str(&msg))?;
+        Ok(())
+    }
 
-                // sum of bit1 (sum of carry's)
-                let l = abc_car << 1 | abc_car_prev >> (BITS_PER_UNIT_GOL - 1);
-                let r = abc_car >> 1;
-                let lr = l ^ r;
-                let sum1 = lr ^ ab_car;
-                let car1 = l & r | lr & ab_car;
+    pub fn load_rle(&mut self, rle: &str) -> Result<(), JsValue> {
+        self.load_rle_result(rle).map_err(|msg| JsValue::from_str(&msg))
+    }
 
-                self.bit_grid.units[unit_index] = (currn | sum0) & (car0 ^ sum1) & !car1;
-                unit_index += 1;
+    pub fn to_rle(&self) -> String {
+        rle::write(&self.to_bit_grid(), Some(&self.rule))
+    }
 
-                abc_sum_prev = abc_sum;
-                abc_car_prev = abc_car;
-            }
+    pub fn place_pattern(
+        &mut self, name: &str, x0: usize, y0: usize, transform: Transform
+    ) -> Result<(), JsValue> {
+        self.place_pattern_result(name, x0, y0, transform).map_err(|msg| JsValue::from_str(&msg))
+    }
 
-            let row_tmp = row_above;
-            row_above = row_currn;
-            row_currn = row_below;
-            row_below = row_tmp;
+    // Returns the objects in the current state. Only meaningful for Conway's rule, as the objects
+    // that are recognised are those of the pattern catalog.
+    pub fn census(&self) -> Census {
+        thread_local! {
+            // Building the catalog of all phases and orientations of the objects is expensive, so
+            // it is only done once for each thread
+            static CENSUS_TAKER: CensusTaker = CensusTaker::new();
         }
+
+        CENSUS_TAKER.with(|census_taker| census_taker.take(&self.to_bit_grid()))
     }
 
     fn unit_index(&self, x: usize, y: usize) -> usize {
@@ -505,9 +1304,56 @@ impl RunStats {
             num_toggled_steps: 0,
             min_cells_after_max: ini_cells,
             min_cells_after_max_steps: 0,
-            num_steps: 0
+            num_steps: 0,
+            period: 0,
+            cycle_start_step: 0,
+            translation_period: 0,
+            translation_dx: 0,
+            translation_dy: 0,
+            translation_start_step: 0,
+            escaped_n: 0,
+            escaped_ne: 0,
+            escaped_e: 0,
+            escaped_se: 0,
+            escaped_s: 0,
+            escaped_sw: 0,
+            escaped_w: 0,
+            escaped_nw: 0,
         }
     }
+
+    // Records a spaceship that escaped in the given direction. Both components of the direction
+    // are -1, 0 or 1, where negative values correspond to west and north respectively.
+    fn record_escape(&mut self, dx: i32, dy: i32) {
+        let count = match (dx, dy) {
+            (0, -1) => &mut self.escaped_n,
+            (1, -1) => &mut self.escaped_ne,
+            (1, 0) => &mut self.escaped_e,
+            (1, 1) => &mut self.escaped_se,
+            (0, 1) => &mut self.escaped_s,
+            (-1, 1) => &mut self.escaped_sw,
+            (-1, 0) => &mut self.escaped_w,
+            (-1, -1) => &mut self.escaped_nw,
+            _ => panic!("Invalid direction ({}, {})", dx, dy),
+        };
+        *count += 1;
+    }
+
+    pub fn num_escaped(&self) -> u16 {
+        self.escaped_n + self.escaped_ne + self.escaped_e + self.escaped_se
+            + self.escaped_s + self.escaped_sw + self.escaped_w + self.escaped_nw
+    }
+}
+
+// Returns a hash of a state as obtained by copy_state. It is only used to quickly tell states
+// apart, so a simple multiplicative hash suffices.
+fn state_hash(state: &[UnitType]) -> u64 {
+    state.iter().fold(0, |hash, &unit| {
+        // Units can be wider than the hash
+        let unit = unit as u128;
+        let bits = unit as u64 ^ (unit >> 64) as u64;
+        (hash.rotate_left(5) ^ bits).wrapping_mul(0x517C_C1B7_2722_0A95)
+    })
 }
 
 #[wasm_bindgen]
@@ -518,8 +1364,71 @@ impl GameOfLifeRunner {
         GameOfLifeRunner {
             min_absolute_dormancy,
             min_relative_dormancy,
+            max_period: DEFAULT_MAX_PERIOD,
             bit_counter: BitCounter::new(),
+            escape_detector: EscapeDetector::new(),
+            history: VecDeque::new(),
+            spare_state: Vec::new(),
+            toggled: BitGrid::new(0, 0),
+            num_toggled: 0,
+            step_limit: 0,
+        }
+    }
+
+    pub fn set_max_period(mut self, max_period: u32) -> Self {
+        self.max_period = max_period;
+        self
+    }
+
+    pub fn max_period(&self) -> u32 {
+        self.max_period
+    }
+
+    // Adds the current state of the CA to the history. When the same state was encountered
+    // before, it returns the step when this happened.
+    fn find_cycle(&mut self, gol: &GameOfLife) -> Option<u32> {
+        let mut units = std::mem::take(&mut self.spare_state);
+        gol.copy_state(&mut units);
+
+        self.record_state(gol, units)
+    }
+
+    // Like find_cycle, for the given state of the CA, as obtained by copy_state
+    fn record_state(&mut self, gol: &GameOfLife, units: Vec<UnitType>) -> Option<u32> {
+        let hash = state_hash(&units);
+
+        let cycle_start = self.history.iter().find(
+            |other| other.hash == hash && other.units == units
+        ).map(|other| other.step);
+
+        self.history.push_back(RecordedState {
+            step: gol.num_steps(),
+            hash,
+            signature: gol.translation_signature(&units),
+            units,
+        });
+        if self.history.len() > self.max_period as usize {
+            self.spare_state = self.history.pop_front().unwrap().units;
+        }
+
+        cycle_start
+    }
+
+    // Checks if the most recent state in the history is a translation of an earlier state. If so,
+    // it returns the step of the earlier state, together with the displacement.
+    fn find_translation(&self, gol: &GameOfLife) -> Option<(u32, i32, i32)> {
+        let latest = self.history.back()?;
+
+        // Check the most recent states first, to find the smallest period
+        for other in self.history.iter().rev().skip(1) {
+            if other.signature == latest.signature {
+                if let Some((dx, dy)) = gol.find_displacement(&other.units, &latest.units) {
+                    return Some((other.step, dx, dy));
+                }
+            }
         }
+
+        None
     }
 
     fn max_steps(&self, steps: u32) -> u32 {
@@ -529,53 +1438,212 @@ impl GameOfLifeRunner {
         max(rel_limit, abs_limit)
     }
 
-    pub fn run(&self, gol: &mut GameOfLife) -> RunStats {
-        let mut stats = RunStats::new(self.bit_counter.count_live_cells(gol) as u16);
-        let mut max_steps = self.max_steps(0);
-        let mut toggled = gol.bit_grid.clone();
+    fn reset_toggled(&mut self, gol: &GameOfLife) {
+        if self.toggled.width == gol.bit_grid.width && self.toggled.height == gol.bit_grid.height {
+            self.toggled.units.copy_from_slice(&gol.bit_grid.units);
+        } else {
+            self.toggled = gol.bit_grid.clone();
+        }
+        self.num_toggled = self.bit_counter.count_live_cells(gol);
+    }
 
-        loop {
-            gol.step();
+    // Updates the statistics for the current state of the CA. Returns true when the CA is dormant,
+    // i.e. none of the statistics changed.
+    fn update_stats(&mut self, stats: &mut RunStats, gol: &GameOfLife) -> bool {
+        let num_cells = self.bit_counter.count_live_cells(gol) as u16;
+        self.num_toggled += self.bit_counter.merge_live_cells(gol, &mut self.toggled);
+
+        self.update_counts(stats, num_cells, gol.num_steps())
+    }
 
-            let mut dormant = true;
-            let num_cells = self.bit_counter.count_live_cells(gol) as u16;
+    // Like update_stats, for the given state of the CA, as obtained by copy_state
+    fn update_stats_from_state(
+        &mut self, stats: &mut RunStats, gol: &GameOfLife, state: &[UnitType]
+    ) -> bool {
+        let num_cells = state.iter().map(|unit| unit.count_ones() as usize).sum::<usize>() as u16;
+        // The toggled cells are tracked in the layout of the grid of the CA, whose rows include a
+        // border row at the top
+        let rows = &mut self.toggled.units[gol.units_per_row..gol.units_per_row * (gol.height + 1)];
+        for (toggled, &unit) in rows.iter_mut().zip(state.iter()) {
+            self.num_toggled += (unit & !*toggled).count_ones() as usize;
+            *toggled |= unit;
+        }
 
-            if num_cells > stats.max_cells {
-                stats.max_cells = num_cells;
-                stats.max_cells_steps = gol.num_steps();
-                stats.min_cells_after_max = num_cells;
-                stats.min_cells_after_max_steps = stats.max_cells_steps;
+        self.update_counts(stats, num_cells, gol.num_steps())
+    }
 
-                dormant = false;
-            } else if num_cells < stats.min_cells_after_max {
-                stats.min_cells_after_max = num_cells;
-                stats.min_cells_after_max_steps = gol.num_steps();
+    // Updates the statistics that are derived from the number of live cells and toggled cells.
+    // Returns true when none of them changed.
+    fn update_counts(&mut self, stats: &mut RunStats, num_cells: u16, num_steps: u32) -> bool {
+        let mut dormant = true;
 
-                dormant = false;
-            }
+        if num_cells > stats.max_cells {
+            stats.max_cells = num_cells;
+            stats.max_cells_steps = num_steps;
+            stats.min_cells_after_max = num_cells;
+            stats.min_cells_after_max_steps = stats.max_cells_steps;
 
-            toggled.or(&gol.bit_grid);
-            let toggled_count = self.bit_counter.count_live_cells_in_bitgrid(gol, &toggled) as u16;
-            if toggled_count > stats.num_toggled {
-                stats.num_toggled = toggled_count;
-                stats.num_toggled_steps = gol.num_steps();
+            dormant = false;
+        } else if num_cells < stats.min_cells_after_max {
+            stats.min_cells_after_max = num_cells;
+            stats.min_cells_after_max_steps = num_steps;
 
-                dormant = false;
-            }
+            dormant = false;
+        }
+
+        let toggled_count = self.num_toggled as u16;
+        if toggled_count > stats.num_toggled {
+            stats.num_toggled = toggled_count;
+            stats.num_toggled_steps = num_steps;
+
+            dormant = false;
+        }
+
+        dormant
+    }
+
+    // Prepares a run of the given CA. Returns the initial statistics.
+    fn start_run(&mut self, gol: &GameOfLife) -> RunStats {
+        let stats = RunStats::new(self.bit_counter.count_live_cells(gol) as u16);
+        self.step_limit = self.max_steps(0);
+        self.reset_toggled(gol);
+
+        self.history.clear();
+        if self.max_period > 0 {
+            self.find_cycle(gol);
+        }
+
+        stats
+    }
+
+    // Updates the statistics after the CA executed a step. Returns true when the run is finished,
+    // in which case the statistics are final.
+    fn finish_step(&mut self, gol: &mut GameOfLife, stats: &mut RunStats) -> bool {
+        if gol.border == GridBorder::Absorbing {
+            self.escape_detector.remove_escaping_spaceships(gol, stats);
+        }
+
+        let dormant = self.update_stats(stats, gol);
+        let cycle_start = if self.max_period > 0 { self.find_cycle(gol) } else { None };
+
+        self.conclude_step(gol, stats, dormant, cycle_start)
+    }
+
+    // Like finish_step, for the given state of the CA, as obtained by copy_state. It is used
+    // instead of the grid of the CA, which does not need to be up to date.
+    fn finish_step_with_state(
+        &mut self, gol: &GameOfLife, state: Vec<UnitType>, stats: &mut RunStats
+    ) -> bool {
+        let dormant = self.update_stats_from_state(stats, gol, &state);
+        let cycle_start = if self.max_period > 0 {
+            self.record_state(gol, state)
+        } else {
+            self.spare_state = state;
+            None
+        };
+
+        self.conclude_step(gol, stats, dormant, cycle_start)
+    }
 
-            if !dormant {
-                max_steps = self.max_steps(gol.num_steps());
-            } else if gol.num_steps() >= max_steps {
+    // Checks if the run is finished, given whether the statistics changed during the step and the
+    // step when the current state was encountered before, if any
+    fn conclude_step(
+        &mut self, gol: &GameOfLife, stats: &mut RunStats, dormant: bool, cycle_start: Option<u32>
+    ) -> bool {
+        if self.max_period > 0 {
+            if let Some(cycle_start_step) = cycle_start {
+                // All states of the cycle have been visited, so the statistics are final
+                stats.period = gol.num_steps() - cycle_start_step;
+                stats.cycle_start_step = cycle_start_step;
                 stats.num_steps = gol.num_steps();
+                return true;
+            }
+
+            // Unlike for a cycle, the run continues when a translation is found, as the moving
+            // pattern can still visit new cells or collide with other cells.
+            if stats.translation_period == 0 {
+                if let Some((start_step, dx, dy)) = self.find_translation(gol) {
+                    stats.translation_period = gol.num_steps() - start_step;
+                    stats.translation_dx = dx;
+                    stats.translation_dy = dy;
+                    stats.translation_start_step = start_step;
+                }
+            }
+        }
+
+        if !dormant {
+            self.step_limit = self.max_steps(gol.num_steps());
+        } else if gol.num_steps() >= self.step_limit {
+            stats.num_steps = gol.num_steps();
+            return true;
+        }
+
+        false
+    }
+
+    pub fn run(&mut self, gol: &mut GameOfLife) -> RunStats {
+        let mut stats = self.start_run(gol);
+
+        loop {
+            gol.step();
+            if self.finish_step(gol, &mut stats) {
                 return stats;
             }
         }
     }
+
+    // Runs the CA for the given number of steps, without early termination. When the CA is
+    // advanced using HashLife, the intermediate states are skipped. The statistics are then based
+    // on samples taken after 1, 2, 4, 8, ... steps.
+    pub fn run_steps(&mut self, gol: &mut GameOfLife, num_steps: u32) -> RunStats {
+        let mut stats = RunStats::new(self.bit_counter.count_live_cells(gol) as u16);
+        let sampled = gol.uses_hashlife(num_steps);
+        self.reset_toggled(gol);
+        // One HashLife is used for the entire run, so that its memoised results are reused
+        let mut hashlife = if sampled { Some(HashLife::from_game_of_life(gol)) } else { None };
+
+        let mut steps_done = 0;
+        let mut chunk_size = 1;
+        while steps_done < num_steps {
+            let n = chunk_size.min(num_steps - steps_done);
+            if let Some(hashlife) = &mut hashlife {
+                gol.advance_with(hashlife, n);
+            } else {
+                gol.advance(n);
+            }
+            if gol.border == GridBorder::Absorbing {
+                // HashLife is not used for this border, so this is done after each step
+                self.escape_detector.remove_escaping_spaceships(gol, &mut stats);
+            }
+            steps_done += n;
+            if sampled {
+                chunk_size = n * 2;
+            }
+
+            self.update_stats(&mut stats, gol);
+        }
+
+        stats.num_steps = gol.num_steps();
+        stats
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64Mcg;
+
+    // Returns the cells of a random pattern of the given size. Each cell is alive with the given
+    // probability.
+    pub(super) fn random_cells(
+        width: usize, height: usize, seed: u64, density: f64
+    ) -> Vec<(usize, usize)> {
+        let mut rng = Pcg64Mcg::seed_from_u64(seed);
+        (0..height).flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter(|_| rng.gen_bool(density))
+            .collect()
+    }
 
     #[test]
     fn basic_bit_count() {
@@ -599,39 +1667,151 @@ mod tests {
         assert_eq!(bc.count_set_bits(&bit_grid), BITS_PER_UNIT * 3);
     }
 
+    fn bit_grid_cells(bit_grid: &BitGrid) -> Vec<(usize, usize)> {
+        let mut cells = Vec::new();
+        for y in 0..bit_grid.height() {
+            for x in 0..bit_grid.width() {
+                if bit_grid.get(x, y) {
+                    cells.push((x, y));
+                }
+            }
+        }
+        cells
+    }
+
+    #[test]
+    fn grid_boolean_operations() {
+        let mut a = BitGrid::new(100, 3);
+        let mut b = BitGrid::new(100, 3);
+        a.set(1, 0);
+        a.set(70, 1);
+        b.set(70, 1);
+        b.set(99, 2);
+
+        let mut or = a.clone();
+        or.or(&b);
+        assert_eq!(bit_grid_cells(&or), vec![(1, 0), (70, 1), (99, 2)]);
+
+        let mut and = a.clone();
+        and.and(&b);
+        assert_eq!(bit_grid_cells(&and), vec![(70, 1)]);
+
+        let mut xor = a.clone();
+        xor.xor(&b);
+        assert_eq!(bit_grid_cells(&xor), vec![(1, 0), (99, 2)]);
+
+        let mut and_not = a.clone();
+        and_not.and_not(&b);
+        assert_eq!(bit_grid_cells(&and_not), vec![(1, 0)]);
+    }
+
+    #[test]
+    fn grid_operations_at_offset() {
+        let mut small = BitGrid::new(3, 2);
+        small.set(0, 0);
+        small.set(2, 1);
+
+        // Straddles a unit boundary
+        let mut grid = BitGrid::new(130, 4);
+        grid.set(63, 1);
+        grid.or_at(&small, 62, 1);
+        assert_eq!(bit_grid_cells(&grid), vec![(62, 1), (63, 1), (64, 2)]);
+
+        // Only the area covered by the other grid is replaced
+        grid.set(65, 1);
+        grid.paste_at(&small, 62, 1);
+        assert_eq!(bit_grid_cells(&grid), vec![(62, 1), (65, 1), (64, 2)]);
+
+        grid.xor_at(&small, 128, 3);
+        assert_eq!(bit_grid_cells(&grid), vec![(62, 1), (65, 1), (64, 2), (128, 3)]);
+
+        grid.and_not_at(&small, 62, 1);
+        assert_eq!(bit_grid_cells(&grid), vec![(65, 1), (128, 3)]);
+
+        // Parts outside the grid are ignored
+        let mut grid = BitGrid::new(10, 10);
+        grid.or_at(&small, -2, -1);
+        assert_eq!(bit_grid_cells(&grid), vec![(0, 0)]);
+
+        grid.and_at(&small, -1, -1);
+        assert_eq!(bit_grid_cells(&grid), Vec::<(usize, usize)>::new());
+    }
+
+    #[test]
+    fn grid_shift_and_crop() {
+        let mut grid = BitGrid::new(80, 5);
+        grid.set(0, 0);
+        grid.set(60, 2);
+        grid.set(79, 4);
+
+        grid.shift(5, 1);
+        assert_eq!(bit_grid_cells(&grid), vec![(5, 1), (65, 3)]);
+
+        grid.shift(-6, -1);
+        assert_eq!(bit_grid_cells(&grid), vec![(59, 2)]);
+
+        let cropped = grid.crop(58, 1, 3, 3);
+        assert_eq!((cropped.width(), cropped.height()), (3, 3));
+        assert_eq!(bit_grid_cells(&cropped), vec![(1, 1)]);
+
+        // Parts of the rectangle outside the grid are empty
+        let cropped = grid.crop(-1, -1, 100, 4);
+        assert_eq!(bit_grid_cells(&cropped), vec![(60, 3)]);
+    }
+
+    #[test]
+    fn grid_bounding_box() {
+        let mut grid = BitGrid::new(150, 10);
+        assert_eq!(grid.bounding_box(), None);
+
+        grid.set(70, 3);
+        assert_eq!(grid.bounding_box(), Some((70, 3, 1, 1)));
+
+        grid.set(10, 7);
+        grid.set(130, 5);
+        assert_eq!(grid.bounding_box(), Some((10, 3, 121, 5)));
+
+        // Padding bits are ignored
+        grid.units.iter_mut().for_each(|unit| *unit = !0 << 10);
+        assert_eq!(grid.bounding_box(), Some((10, 0, 140, 10)));
+    }
+
     mod game_of_life {
         use super::super::*;
+        use super::random_cells;
+        use rand::{Rng, SeedableRng};
+        use rand_pcg::Pcg64Mcg;
 
         fn add_glider(gol: &mut GameOfLife, x: usize, y: usize) {
-            // Glider pattern:
-            //    *
-            //      *
-            //  * * *
-            gol.set(1 + x, 0 + y);
-            gol.set(2 + x, 1 + y);
-            gol.set(0 + x, 2 + y);
-            gol.set(1 + x, 2 + y);
-            gol.set(2 + x, 2 + y);
+            gol.place_pattern_result("glider", x, y, Transform::Identity).unwrap();
+        }
+
+        fn add_leftwards_glider(gol: &mut GameOfLife, x: usize, y: usize) {
+            gol.place_pattern_result("glider", x, y, Transform::FlipHorizontal).unwrap();
         }
 
-        fn add_leftwards_glider(gol: &mut GameOfLife, x: usize, y: usize) {
-            // Glider pattern:
-            //    *
-            //  *
-            //  * * *
-            gol.set(1 + x, 0 + y);
-            gol.set(0 + x, 1 + y);
-            gol.set(0 + x, 2 + y);
-            gol.set(1 + x, 2 + y);
-            gol.set(2 + x, 2 + y);
-        }
+        fn add_blinker(gol: &mut GameOfLife, x: usize, y: usize) {
+            gol.place_pattern_result("blinker", x, y, Transform::Identity).unwrap();
+        }
+
+        #[test]
+        fn paste_bit_grid() {
+            let mut bit_grid = BitGrid::new(70, 2);
+            for x in 0..70 {
+                bit_grid.set(x, x % 2);
+            }
+
+            for &x0 in &[0, 1, 61, 62, 63, 100] {
+                let mut gol = GameOfLife::new(200, 4, false);
+                gol.paste(&bit_grid, x0, 1).unwrap();
+
+                let mut expected = BitGrid::new(200, 4);
+                expected.paste_at(&bit_grid, x0 as isize, 1);
+                assert_eq!(gol.to_bit_grid().units, expected.units, "x0 = {}", x0);
+            }
 
-        fn add_blinker(gol: &mut GameOfLife, x: usize, y: usize) {
-            // Blinker pattern:
-            //   * * *
-            gol.set(0 + x, y);
-            gol.set(1 + x, y);
-            gol.set(2 + x, y);
+            let mut gol = GameOfLife::new(200, 4, false);
+            assert!(gol.paste(&bit_grid, 131, 0).is_err());
         }
 
         #[test]
@@ -706,6 +1886,165 @@ mod tests {
             assert!(gol.bit_grid.get(0, 6));
         }
 
+        // Returns if the border cell at the given position is set. Positions are relative to the
+        // top-left cell of the grid, so the border surrounds positions (0, 0) to (w - 1, h - 1).
+        fn border_cell(gol: &GameOfLife, x: isize, y: isize) -> bool {
+            gol.bit_grid.get((x + 1) as usize, (y + 1) as usize)
+        }
+
+        fn generic_border_matches_wrapped(w: usize, h: usize) {
+            let mut gol1 = GameOfLife::new(w, h, true);
+            for y in 0..h {
+                for x in 0..w {
+                    if (x * 7 + y * 13) % 5 < 2 {
+                        gol1.set(x, y);
+                    }
+                }
+            }
+            let mut gol2 = GameOfLife::new(w, h, true);
+            gol2.bit_grid = gol1.bit_grid.clone();
+
+            let (wi, hi) = (w as isize, h as isize);
+            gol1.restore_right_bits();
+            gol1.set_wrapped_border();
+            gol2.restore_right_bits();
+            gol2.set_border_from(|x, y| (x.rem_euclid(wi), y.rem_euclid(hi)));
+
+            // Compare all bits that are used, i.e. excluding those right of the right border
+            let (_, _, mask_r) = gol1.unit_masks();
+            let mask_last = mask_r | mask_r << 1 | 1;
+            let units = gol1.bit_grid.units.iter().zip(gol2.bit_grid.units.iter());
+            for (i, (u1, u2)) in units.enumerate() {
+                let last = i % gol1.units_per_row == gol1.units_per_row - 1;
+                let mask = if last { mask_last } else { !0 };
+                assert_eq!(u1 & mask, u2 & mask, "Mismatch at unit {} (w={}, h={})", i, w, h);
+            }
+        }
+
+        #[test]
+        fn generic_border_matches_wrapped_sizes() {
+            for &size in [5, 61, 62, 63, 64, 125, 126].iter() {
+                generic_border_matches_wrapped(size, 7);
+            }
+        }
+
+        #[test]
+        fn klein_bottle_border() {
+            let (w, h) = (7, 5);
+            let mut gol = GameOfLife::new_result(w, h, GridBorder::KleinBottle).unwrap();
+
+            gol.set(1, 0); // Top row
+            gol.set(0, 2); // Left column
+            gol.set(w - 1, h - 1); // Corner
+            gol.set_border_bits();
+
+            let (wi, hi) = (w as isize, h as isize);
+            // Flipped horizontally when wrapping vertically
+            assert!(border_cell(&gol, wi - 2, hi));
+            assert!(border_cell(&gol, 0, -1));
+            // Not flipped when wrapping horizontally
+            assert!(border_cell(&gol, wi, 2));
+            assert!(border_cell(&gol, -1, hi - 1));
+            // The corner is wrapped in both directions
+            assert!(border_cell(&gol, wi, -1));
+            assert!(!border_cell(&gol, -1, -1));
+            assert!(!border_cell(&gol, 1, hi));
+            assert!(!border_cell(&gol, wi, hi));
+        }
+
+        #[test]
+        fn projective_plane_border() {
+            let (w, h) = (7, 5);
+            let mut gol = GameOfLife::new_result(w, h, GridBorder::ProjectivePlane).unwrap();
+
+            gol.set(1, 0); // Top row
+            gol.set(0, 1); // Left column
+            gol.set_border_bits();
+
+            let (wi, hi) = (w as isize, h as isize);
+            assert!(border_cell(&gol, wi - 2, hi));
+            assert!(border_cell(&gol, wi, hi - 2));
+            assert!(!border_cell(&gol, 1, hi));
+            assert!(!border_cell(&gol, wi, 1));
+        }
+
+        #[test]
+        fn mirror_border() {
+            let (w, h) = (7, 5);
+            let mut gol = GameOfLife::new_result(w, h, GridBorder::Mirror).unwrap();
+
+            gol.set(0, 0);
+            gol.set(3, h - 1);
+            gol.set_border_bits();
+
+            let (wi, hi) = (w as isize, h as isize);
+            assert!(border_cell(&gol, -1, -1));
+            assert!(border_cell(&gol, -1, 0));
+            assert!(border_cell(&gol, 0, -1));
+            assert!(border_cell(&gol, 3, hi));
+            assert!(!border_cell(&gol, 3, -1));
+            assert!(!border_cell(&gol, wi, 0));
+        }
+
+        #[test]
+        fn alive_border() {
+            let (w, h) = (70, 5);
+            let mut gol = GameOfLife::new_result(w, h, GridBorder::Alive).unwrap();
+
+            gol.step();
+
+            // Cells next to the border have three live neighbours, except those in the corners
+            for x in 1..w - 1 {
+                assert!(gol.get(x, 0));
+                assert!(gol.get(x, h - 1));
+            }
+            for y in 1..h - 1 {
+                assert!(gol.get(0, y));
+                assert!(gol.get(w - 1, y));
+            }
+            assert!(!gol.get(0, 0));
+            assert!(!gol.get(w - 1, h - 1));
+            assert!(!gol.get(1, 1));
+        }
+
+        #[test]
+        fn skipping_inactive_regions_matches_full_step() {
+            let mut rng = Pcg64Mcg::seed_from_u64(7);
+            let borders = [
+                GridBorder::Zeroes, GridBorder::Wrapped, GridBorder::KleinBottle, GridBorder::Alive
+            ];
+
+            for &(w, h) in [(8, 8), (64, 64), (130, 20)].iter() {
+                for &border in borders.iter() {
+                    for &rulestring in ["B3/S23", "B36/S23", "B2/S", "B03/S23"].iter() {
+                        let mut gol1 = GameOfLife::with_border(w, h, border);
+                        let mut gol2 = GameOfLife::with_border(w, h, border);
+                        gol2.skip_inactive = false;
+                        gol1.set_rulestring(rulestring).unwrap();
+                        gol2.set_rulestring(rulestring).unwrap();
+
+                        // A sparse pattern, with a glider heading for the bottom-right corner
+                        add_glider(&mut gol1, w - 8, h - 8);
+                        add_glider(&mut gol2, w - 8, h - 8);
+                        for _ in 0..4 {
+                            let (x, y) = (rng.gen_range(0..w - 2), rng.gen_range(0..h - 2));
+                            add_blinker(&mut gol1, x, y);
+                            add_blinker(&mut gol2, x, y);
+                        }
+
+                        for _ in 0..40 {
+                            gol1.step();
+                            gol2.step();
+                            assert_eq!(
+                                gol1.bit_grid.units, gol2.bit_grid.units,
+                                "{}x{} {:?} {}", w, h, border, rulestring
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
         #[test]
         fn evolve_block() {
             let mut gol = GameOfLife::new(4, 4, true);
@@ -876,7 +2215,7 @@ mod tests {
             // Glider should have moved back to its starting position
             assert!(gol.get(x + 1, 0));
             assert!(gol.get(x + 2, 1));
-            assert!(gol.get(x + 0, 2));
+            assert!(gol.get(x, 2));
             assert!(gol.get(x + 1, 2));
             assert!(gol.get(x + 2, 2));
         }
@@ -942,10 +2281,161 @@ mod tests {
             evolve_glider_against_zeroes_border(64);
         }
 
+        #[test]
+        fn evolve_highlife_birth_on_six() {
+            let mut gol = GameOfLife::new(6, 6, false);
+            gol.set_rule("B36/S23".parse().unwrap());
+
+            for x in 1..4 {
+                gol.set(x, 1);
+                gol.set(x, 3);
+            }
+
+            gol.step();
+
+            // Centre cell has six neighbours, so it is born in HighLife (but not in Conway's Life)
+            assert!(gol.get(2, 2));
+        }
+
+        #[test]
+        fn evolve_seeds() {
+            let mut gol = GameOfLife::new(6, 6, true);
+            let bc = BitCounter::new();
+            gol.set_rulestring("B2/S").unwrap();
+
+            gol.set(1, 2);
+            gol.set(2, 2);
+
+            gol.step();
+
+            // Both cells die, and the four cells with two neighbours are born
+            assert_eq!(bc.count_live_cells(&gol), 4);
+            assert!(gol.get(1, 1));
+            assert!(gol.get(2, 1));
+            assert!(gol.get(1, 3));
+            assert!(gol.get(2, 3));
+        }
+
+        #[test]
+        fn generic_rule_matches_conway_fast_path() {
+            let w = BITS_PER_UNIT + 10;
+            let mut gol1 = GameOfLife::new(w, 12, true);
+            let mut gol2 = GameOfLife::new(w, 12, true);
+
+            add_glider(&mut gol1, BITS_PER_UNIT - 3, 1);
+            add_blinker(&mut gol1, 3, 6);
+            add_leftwards_glider(&mut gol1, 20, 7);
+            add_glider(&mut gol2, BITS_PER_UNIT - 3, 1);
+            add_blinker(&mut gol2, 3, 6);
+            add_leftwards_glider(&mut gol2, 20, 7);
+
+            for _ in 0..50 {
+                gol1.step();
+                gol2.step_generic();
+
+                for x in 0..w {
+                    for y in 0..12 {
+                        assert_eq!(gol1.get(x, y), gol2.get(x, y));
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn rle_round_trip() {
+            let mut gol = GameOfLife::new(8, 6, false);
+            gol.set_rulestring("B36/S23").unwrap();
+            add_glider(&mut gol, 1, 1);
+            add_blinker(&mut gol, 4, 4);
+
+            let rle = gol.to_rle();
+            assert_eq!(rle, "x = 8, y = 6, rule = B36/S23\n$2bo$3bo$b3o$4b3o!\n");
+
+            let mut gol2 = GameOfLife::new(8, 6, false);
+            gol2.load_rle_result(&rle).unwrap();
+
+            assert_eq!(gol2.rule(), gol.rule());
+            assert_eq!(gol2.to_bit_grid(), gol.to_bit_grid());
+        }
+
+        #[test]
+        fn load_rle_centers_pattern() {
+            let mut gol = GameOfLife::new(7, 7, true);
+            let bc = BitCounter::new();
+
+            gol.load_rle_result("x = 3, y = 1\n3o!").unwrap();
+
+            assert_eq!(bc.count_live_cells(&gol), 3);
+            assert!(gol.get(2, 3));
+            assert!(gol.get(3, 3));
+            assert!(gol.get(4, 3));
+            assert!(gol.load_rle_result("x = 8, y = 1\n8o!").is_err());
+        }
+
+        fn assert_plaintext_round_trip(gol: &GameOfLife) {
+            let bit_grid = gol.to_bit_grid();
+            let text = bit_grid.to_plaintext();
+
+            assert_eq!(BitGrid::from_plaintext_result(&text).unwrap(), bit_grid);
+        }
+
+        // Life 1.06 does not preserve the size of the grid. Its bounding box is restored at the
+        // given position.
+        fn assert_life106_round_trip(gol: &GameOfLife, x0: usize, y0: usize) {
+            let text = gol.to_bit_grid().to_life106();
+            let pattern = BitGrid::from_life106_result(&text).unwrap();
+
+            let mut gol2 = GameOfLife::new(gol.width(), gol.height(), false);
+            gol2.paste(&pattern, x0, y0).unwrap();
+
+            assert_eq!(gol2.to_bit_grid(), gol.to_bit_grid());
+        }
+
+        #[test]
+        fn plaintext_round_trip_glider() {
+            let mut gol = GameOfLife::new(7, 6, false);
+            add_glider(&mut gol, 2, 1);
+
+            assert_eq!(
+                gol.to_bit_grid().to_plaintext(),
+                ".......\n...O...\n....O..\n..OOO..\n.......\n.......\n"
+            );
+            assert_plaintext_round_trip(&gol);
+        }
+
+        #[test]
+        fn plaintext_round_trip_blinker() {
+            let mut gol = GameOfLife::new(5, 5, false);
+            add_blinker(&mut gol, 1, 2);
+            assert_plaintext_round_trip(&gol);
+
+            gol.step();
+            assert_plaintext_round_trip(&gol);
+        }
+
+        #[test]
+        fn life106_round_trip_glider() {
+            let mut gol = GameOfLife::new(7, 6, false);
+            add_glider(&mut gol, 2, 1);
+
+            assert_eq!(gol.to_bit_grid().to_life106(), "#Life 1.06\n3 1\n4 2\n2 3\n3 3\n4 3\n");
+            assert_life106_round_trip(&gol, 2, 1);
+        }
+
+        #[test]
+        fn life106_round_trip_blinker() {
+            let mut gol = GameOfLife::new(5, 5, false);
+            add_blinker(&mut gol, 1, 2);
+            assert_life106_round_trip(&gol, 1, 2);
+
+            gol.step();
+            assert_life106_round_trip(&gol, 2, 1);
+        }
+
         #[test]
         fn glider_termination() {
             let mut gol = GameOfLife::new(5, 5, true);
-            let runner = GameOfLifeRunner::new(20, 2.0);
+            let mut runner = GameOfLifeRunner::new(20, 2.0);
 
             add_glider(&mut gol, 1, 1);
 
@@ -958,7 +2448,7 @@ mod tests {
         #[test]
         fn penta_decathlon_termination() {
             let mut gol = GameOfLife::new(20, 15, true);
-            let runner = GameOfLifeRunner::new(20, 2.0);
+            let mut runner = GameOfLifeRunner::new(20, 2.0);
 
             for i in 5..15 {
                 if i == 7 || i == 12 {
@@ -975,11 +2465,291 @@ mod tests {
             assert!(stats.max_cells_steps < 15);
         }
 
+        #[test]
+        fn still_life_cycle_detection() {
+            let mut gol = GameOfLife::new(8, 8, false);
+            let mut runner = GameOfLifeRunner::new(20, 2.0);
+
+            // Block
+            gol.set(3, 3);
+            gol.set(4, 3);
+            gol.set(3, 4);
+            gol.set(4, 4);
+
+            let stats = runner.run(&mut gol);
+
+            assert_eq!(stats.period, 1);
+            assert_eq!(stats.cycle_start_step, 0);
+            assert_eq!(stats.num_steps, 1);
+        }
+
+        #[test]
+        fn oscillator_cycle_detection() {
+            let mut gol = GameOfLife::new(12, 12, false);
+            let mut runner = GameOfLifeRunner::new(20, 2.0);
+
+            // Pre-block, which turns into a block after one step
+            gol.set(2, 2);
+            gol.set(3, 2);
+            gol.set(2, 3);
+            // Blinker
+            add_blinker(&mut gol, 7, 8);
+
+            let stats = runner.run(&mut gol);
+
+            assert_eq!(stats.period, 2);
+            assert_eq!(stats.cycle_start_step, 1);
+            assert_eq!(stats.num_steps, 3);
+        }
+
+        #[test]
+        fn glider_cycle_on_wrapped_grid() {
+            let size = 12;
+            let mut gol = GameOfLife::new(size, size, true);
+            let mut runner = GameOfLifeRunner::new(20, 2.0);
+
+            add_glider(&mut gol, 1, 1);
+
+            let stats = runner.run(&mut gol);
+
+            assert_eq!(stats.period as usize, size * 4);
+            assert_eq!(stats.cycle_start_step, 0);
+            assert_eq!(stats.num_steps as usize, size * 4);
+            assert_eq!(stats.num_toggled as usize, size * 4);
+        }
+
+        #[test]
+        fn cycle_exceeding_max_period() {
+            let size = 12;
+            let mut gol = GameOfLife::new(size, size, true);
+            let mut runner = GameOfLifeRunner::new(20, 2.0).set_max_period(47);
+
+            add_glider(&mut gol, 1, 1);
+
+            let stats = runner.run(&mut gol);
+
+            assert_eq!(stats.period, 0);
+            assert!((stats.num_steps as usize) >= size * 4 * 2);
+        }
+
+        #[test]
+        fn glider_translation_on_wrapped_grid() {
+            let mut gol = GameOfLife::new(12, 12, true);
+            let mut runner = GameOfLifeRunner::new(20, 2.0);
+
+            add_glider(&mut gol, 1, 1);
+
+            let stats = runner.run(&mut gol);
+
+            assert_eq!(stats.translation_period, 4);
+            assert_eq!(stats.translation_dx, 1);
+            assert_eq!(stats.translation_dy, 1);
+            assert_eq!(stats.translation_start_step, 0);
+        }
+
+        #[test]
+        fn leftwards_glider_translation_across_wrapped_border() {
+            let mut gol = GameOfLife::new(BITS_PER_UNIT * 2, 8, true);
+            let mut runner = GameOfLifeRunner::new(20, 2.0);
+
+            add_leftwards_glider(&mut gol, 0, 5);
+
+            let stats = runner.run(&mut gol);
+
+            assert_eq!(stats.translation_period, 4);
+            assert_eq!(stats.translation_dx, -1);
+            assert_eq!(stats.translation_dy, 1);
+        }
+
+        #[test]
+        fn glider_translation_across_klein_bottle_border() {
+            let mut gol = GameOfLife::new_result(12, 12, GridBorder::KleinBottle).unwrap();
+            let mut runner = GameOfLifeRunner::new(20, 2.0);
+
+            // Crosses the bottom edge, where it is flipped horizontally, in the first period
+            add_glider(&mut gol, 4, 9);
+
+            let stats = runner.run(&mut gol);
+
+            assert_eq!(stats.translation_period, 4);
+            assert_eq!(stats.translation_dx, 1);
+            assert_eq!(stats.translation_dy, 1);
+            assert_eq!(stats.translation_start_step, 0);
+        }
+
+        #[test]
+        fn glider_translation_across_projective_plane_border() {
+            let mut gol = GameOfLife::new_result(12, 12, GridBorder::ProjectivePlane).unwrap();
+            let mut runner = GameOfLifeRunner::new(20, 2.0);
+
+            // Crosses the left edge, where it is flipped vertically, in the first period
+            add_leftwards_glider(&mut gol, 0, 4);
+
+            let stats = runner.run(&mut gol);
+
+            assert_eq!(stats.translation_period, 4);
+            assert_eq!(stats.translation_dx, -1);
+            assert_eq!(stats.translation_dy, 1);
+            assert_eq!(stats.translation_start_step, 0);
+        }
+
+        #[test]
+        fn glider_translation_before_zeroes_border() {
+            let mut gol = GameOfLife::new(20, 20, false);
+            let mut runner = GameOfLifeRunner::new(20, 2.0);
+
+            add_glider(&mut gol, 1, 1);
+
+            let stats = runner.run(&mut gol);
+
+            assert_eq!(stats.translation_period, 4);
+            assert_eq!(stats.translation_dx, 1);
+            assert_eq!(stats.translation_dy, 1);
+            // The glider turns into a block at the border
+            assert_eq!(stats.period, 1);
+        }
+
+        #[test]
+        fn no_translation_for_spaceship_leaving_debris() {
+            let mut gol = GameOfLife::new(24, 24, false);
+            let mut runner = GameOfLifeRunner::new(20, 2.0);
+
+            add_glider(&mut gol, 1, 1);
+            gol.place_pattern_result("block", 20, 2, Transform::Identity).unwrap();
+
+            let stats = runner.run(&mut gol);
+
+            // Only part of the state moves, so it is never a translation of an earlier state
+            assert_eq!(stats.translation_period, 0);
+            assert_eq!(stats.period, 1);
+        }
+
+        #[test]
+        fn no_translation_for_oscillator() {
+            let mut gol = GameOfLife::new(8, 8, true);
+            let mut runner = GameOfLifeRunner::new(20, 2.0);
+
+            add_blinker(&mut gol, 2, 2);
+
+            let stats = runner.run(&mut gol);
+
+            assert_eq!(stats.period, 2);
+            assert_eq!(stats.translation_period, 0);
+        }
+
+        #[test]
+        fn glider_escapes_absorbing_border() {
+            let mut gol = GameOfLife::new_result(20, 16, GridBorder::Absorbing).unwrap();
+            let mut runner = GameOfLifeRunner::new(20, 2.0);
+
+            add_glider(&mut gol, 10, 9);
+            add_leftwards_glider(&mut gol, 5, 2);
+            add_blinker(&mut gol, 14, 3);
+
+            let stats = runner.run(&mut gol);
+
+            assert_eq!(stats.escaped_se, 1);
+            assert_eq!(stats.escaped_sw, 1);
+            assert_eq!(stats.num_escaped(), 2);
+            // Only the blinker remains
+            assert_eq!(stats.period, 2);
+            assert_eq!(BitCounter::new().count_live_cells(&gol), 3);
+        }
+
+        #[test]
+        fn lwss_escapes_absorbing_border() {
+            let mut gol = GameOfLife::new_result(24, 12, GridBorder::Absorbing).unwrap();
+            let mut runner = GameOfLifeRunner::new(20, 2.0);
+
+            // Westward LWSS
+            let lwss = BitGrid::from_plaintext_result(".O..O\nO....\nO...O\nOOOO.").unwrap();
+            gol.paste(&lwss, 10, 4).unwrap();
+
+            let stats = runner.run(&mut gol);
+
+            assert_eq!(stats.escaped_w, 1);
+            assert_eq!(stats.num_escaped(), 1);
+            assert_eq!(BitCounter::new().count_live_cells(&gol), 0);
+        }
+
+        #[test]
+        fn glider_does_not_escape_zeroes_border() {
+            let mut gol = GameOfLife::new(20, 16, false);
+            let mut runner = GameOfLifeRunner::new(20, 2.0);
+
+            add_glider(&mut gol, 3, 3);
+
+            let stats = runner.run(&mut gol);
+
+            assert_eq!(stats.num_escaped(), 0);
+            // The glider turned into a block
+            assert_eq!(BitCounter::new().count_live_cells(&gol), 4);
+        }
+
+        #[test]
+        fn advance_with_hashlife() {
+            let mut gol1 = GameOfLife::new(90, 70, false);
+            add_glider(&mut gol1, 3, 5);
+            add_glider(&mut gol1, 40, 20);
+            add_blinker(&mut gol1, 60, 50);
+            let mut gol2 = GameOfLife::new(90, 70, false);
+            gol2.bit_grid = gol1.bit_grid.clone();
+
+            assert!(gol1.uses_hashlife(HASHLIFE_MIN_STEPS));
+            gol1.advance(HASHLIFE_MIN_STEPS);
+            for _ in 0..HASHLIFE_MIN_STEPS {
+                gol2.step();
+            }
+
+            assert_eq!(gol1.num_steps(), gol2.num_steps());
+            assert_eq!(gol1.to_bit_grid(), gol2.to_bit_grid());
+        }
+
+        #[test]
+        fn advance_matches_step_for_rules() {
+            for rule in ["B3/S23", "B36/S23", "B03/S23"].iter() {
+                let mut gol1 = GameOfLife::new(32, 32, false);
+                gol1.set_rule(rule.parse().unwrap());
+                add_glider(&mut gol1, 1, 1);
+                let mut gol2 = GameOfLife::new(32, 32, false);
+                gol2.set_rule(rule.parse().unwrap());
+                gol2.bit_grid = gol1.bit_grid.clone();
+
+                gol1.advance(1024);
+                for _ in 0..1024 {
+                    gol2.step();
+                }
+
+                assert_eq!(gol1.to_bit_grid(), gol2.to_bit_grid(), "{}", rule);
+            }
+
+            // Rules with births on zero neighbours fall back to step()
+            let mut gol = GameOfLife::new(32, 32, false);
+            gol.set_rule("B03/S23".parse().unwrap());
+            assert!(!gol.uses_hashlife(HASHLIFE_MIN_STEPS));
+        }
+
+        #[test]
+        fn run_steps_sampled() {
+            let mut gol = GameOfLife::new(32, 32, false);
+            let mut runner = GameOfLifeRunner::new(20, 2.0);
+
+            add_glider(&mut gol, 1, 1);
+
+            let stats = runner.run_steps(&mut gol, 5000);
+
+            assert_eq!(stats.num_steps, 5000);
+            assert_eq!(stats.ini_cells, 5);
+            // The glider turned into a block when it reached the border
+            assert_eq!(stats.min_cells_after_max, 4);
+        }
+
         #[test]
         fn glider_toggled_count() {
             let size = 12;
             let mut gol = GameOfLife::new(size, size, true);
-            let runner = GameOfLifeRunner::new(20, 2.0);
+            // Disable cycle detection to verify termination based on dormancy
+            let mut runner = GameOfLifeRunner::new(20, 2.0).set_max_period(0);
 
             add_glider(&mut gol, 1, 1);
 
@@ -988,5 +2758,54 @@ mod tests {
             assert!((stats.num_steps as usize) >= size * 4 * 2);
             assert!((stats.num_toggled as usize) == size * 4);
         }
+
+        #[test]
+        fn toggled_tracking_matches_full_count() {
+            // The same runner is used for all runs, so that its buffers are reused
+            let mut runner = GameOfLifeRunner::new(50, 1.0);
+            let mut seed = 0;
+
+            for &(w, h) in [(10, 10), (70, 20), (70, 20), (30, 40), (126, 8)].iter() {
+                for &border in [GridBorder::Zeroes, GridBorder::Wrapped].iter() {
+                    seed += 1;
+                    let cells = random_cells(w, h, seed, 1.0 / 3.0);
+                    let new_gol = || {
+                        let mut gol = GameOfLife::with_border(w, h, border);
+                        cells.iter().for_each(|&(x, y)| gol.set(x, y));
+                        gol
+                    };
+
+                    let stats = runner.run(&mut new_gol());
+
+                    // Recount all cells that were ever alive after each step
+                    let mut gol = new_gol();
+                    let mut toggled = gol.to_bit_grid();
+                    let bc = BitCounter::new();
+                    let (mut num_toggled, mut num_toggled_steps) = (cells.len(), 0);
+                    while gol.num_steps() < stats.num_steps {
+                        gol.step();
+                        toggled.or(&gol.to_bit_grid());
+                        if bc.count_set_bits(&toggled) > num_toggled {
+                            num_toggled = bc.count_set_bits(&toggled);
+                            num_toggled_steps = gol.num_steps();
+                        }
+                    }
+
+                    assert_eq!(stats.num_toggled as usize, num_toggled, "{}x{}", w, h);
+                    assert_eq!(stats.num_toggled_steps, num_toggled_steps, "{}x{}", w, h);
+                }
+            }
+        }
     }
-}
\ No newline at end of file
+}
+
+pub mod batch;
+pub mod census;
+mod escape;
+pub mod hashlife;
+pub mod immigration;
+pub mod life106;
+pub mod patterns;
+pub mod plaintext;
+pub mod rle;
+pub mod rule;
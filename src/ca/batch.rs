@@ -0,0 +1,272 @@
+// Simultaneous runs of many small CAs. The gardens are packed side by side in one wide grid,
+// separated by isolation columns. These columns are cleared before each step, so that each garden
+// evolves exactly as if it were surrounded by a border of zeroes. Compared to running the gardens
+// one at a time, fewer bits are wasted on padding and the overhead of each step is shared.
+//
+// The gardens stay in the packed grid while they run. After each step, the state of each garden
+// is extracted from it once, and all statistics are derived from that state, exactly as for a
+// single run. A garden only receives its state when its run ends. As runs end, the remaining
+// gardens are packed together again, so that the finished ones do not slow down subsequent steps.
+use super::{
+    GameOfLife, GameOfLifeRunner, GridBorder, RunStats, UnitType, BITS_PER_UNIT_GOL
+};
+
+// The mask for the bits of a unit that represent cells
+const CELL_BITS: UnitType = !(1 << BITS_PER_UNIT_GOL);
+
+pub struct BatchRunner {
+    min_absolute_dormancy: u32,
+    min_relative_dormancy: f32,
+    max_period: u32,
+
+    // One runner for each garden. They are kept, so that their buffers are reused by later batches
+    runners: Vec<GameOfLifeRunner>,
+}
+
+// Extracts the state of the garden from the area of the packed grid that starts at column x0. The
+// state has the layout of those obtained by GameOfLife::copy_state.
+fn extract_state(packed: &GameOfLife, x0: usize, garden: &GameOfLife, state: &mut Vec<UnitType>) {
+    let (mask_l, mask_c, mask_r) = garden.unit_masks();
+    let units_per_row = garden.units_per_row;
+    // Bit N of each unit of the garden represents column N - 1 of the garden. Position P of a row
+    // of the packed grid, i.e. bit P % BITS_PER_UNIT_GOL of unit P / BITS_PER_UNIT_GOL, represents
+    // its column P - 1. All units of the garden are therefore taken from the same offset.
+    let first_index = x0 / BITS_PER_UNIT_GOL;
+    let offset = x0 % BITS_PER_UNIT_GOL;
+
+    state.clear();
+    for packed_row in packed.bit_grid.units.chunks_exact(packed.units_per_row).skip(1).take(
+        garden.height
+    ) {
+        for col in 0..units_per_row {
+            let mask = if col == units_per_row - 1 {
+                mask_r
+            } else if col == 0 {
+                mask_l
+            } else {
+                mask_c
+            };
+
+            let index = first_index + col;
+            let mut bits = (packed_row[index] & CELL_BITS) >> offset;
+            if offset > 0 && index + 1 < packed_row.len() {
+                bits |= (packed_row[index + 1] & CELL_BITS) << (BITS_PER_UNIT_GOL - offset);
+            }
+            state.push(bits & mask);
+        }
+    }
+}
+
+// Sets the state of the garden to that of the area of the packed grid that starts at column x0
+fn copy_garden(packed: &GameOfLife, x0: usize, garden: &mut GameOfLife) {
+    let mut state = Vec::with_capacity(garden.units_per_row * garden.height);
+    extract_state(packed, x0, garden, &mut state);
+
+    let rows = garden.units_per_row..garden.units_per_row * (garden.height + 1);
+    garden.bit_grid.units[rows].copy_from_slice(&state);
+}
+
+// Returns for each unit column of the packed grid a mask with the bits of the given columns
+fn column_masks(packed: &GameOfLife, columns: impl Iterator<Item = usize>) -> Vec<UnitType> {
+    let mut masks = vec![0; packed.units_per_row];
+
+    for x in columns {
+        masks[(x + 1) / BITS_PER_UNIT_GOL] |= 1 << ((x + 1) % BITS_PER_UNIT_GOL);
+    }
+
+    masks
+}
+
+// Clears the given columns of the packed grid
+fn clear_columns(packed: &mut GameOfLife, masks: &[UnitType]) {
+    for row in packed.bit_grid.units.chunks_exact_mut(packed.units_per_row) {
+        for (unit, mask) in row.iter_mut().zip(masks.iter()) {
+            *unit &= !mask;
+        }
+    }
+}
+
+// Packs the given gardens side by side in one grid, each at the given position. Returns this grid,
+// together with the masks of its isolation columns.
+fn pack(gardens: &[GameOfLife], slots: &[(usize, usize)]) -> (GameOfLife, Vec<UnitType>) {
+    let first = &gardens[slots[0].0];
+    let spacing = first.width + 1;
+
+    let mut packed = GameOfLife::new_result(
+        spacing * slots.len() - 1, first.height, GridBorder::Zeroes
+    ).unwrap();
+    packed.set_rule(first.rule);
+    for &(i, slot) in slots.iter() {
+        packed.paste(&gardens[i].to_bit_grid(), spacing * slot, 0).unwrap();
+    }
+
+    let isolation_masks = column_masks(&packed, (1..slots.len()).map(|slot| spacing * slot - 1));
+    (packed, isolation_masks)
+}
+
+impl BatchRunner {
+    pub fn new(min_absolute_dormancy: u32, min_relative_dormancy: f32) -> Self {
+        BatchRunner {
+            min_absolute_dormancy,
+            min_relative_dormancy,
+            max_period: GameOfLifeRunner::new(0, 0.0).max_period(),
+            runners: Vec::new(),
+        }
+    }
+
+    pub fn set_max_period(mut self, max_period: u32) -> Self {
+        self.max_period = max_period;
+        self
+    }
+
+    fn ensure_runners(&mut self, num_runners: usize) {
+        while self.runners.len() < num_runners {
+            self.runners.push(
+                GameOfLifeRunner::new(self.min_absolute_dormancy, self.min_relative_dormancy)
+                    .set_max_period(self.max_period)
+            );
+        }
+    }
+
+    /// Runs all gardens until each of them terminates, as GameOfLifeRunner::run does. Afterwards,
+    /// each garden contains its final state. Returns the statistics of each run.
+    ///
+    /// Gardens are only packed together when all have the same size and rule and have a border of
+    /// zeroes. Otherwise, they are run one at a time.
+    pub fn run(&mut self, gardens: &mut [GameOfLife]) -> Vec<RunStats> {
+        self.ensure_runners(gardens.len());
+
+        let packable = gardens.first().is_some_and(|first| gardens.iter().all(|garden| {
+            garden.border == GridBorder::Zeroes
+                && garden.width == first.width
+                && garden.height == first.height
+                && garden.rule == first.rule
+        }));
+        if !packable {
+            return gardens.iter_mut().zip(self.runners.iter_mut()).map(
+                |(garden, runner)| runner.run(garden)
+            ).collect();
+        }
+
+        let spacing = gardens[0].width + 1;
+        let mut stats: Vec<RunStats> = gardens.iter().zip(self.runners.iter_mut()).map(
+            |(garden, runner)| runner.start_run(garden)
+        ).collect();
+
+        // The indices of the gardens that are still running, together with their position in the
+        // packed grid
+        let mut running: Vec<(usize, usize)> = (0..gardens.len()).map(|i| (i, i)).collect();
+        let (mut packed, mut isolation_masks) = pack(gardens, &running);
+        let mut num_packed = running.len();
+
+        while !running.is_empty() {
+            clear_columns(&mut packed, &isolation_masks);
+            packed.step();
+
+            let mut finished = Vec::new();
+            running.retain(|&(i, slot)| {
+                let garden = &mut gardens[i];
+                let runner = &mut self.runners[i];
+                let mut state = std::mem::take(&mut runner.spare_state);
+                extract_state(&packed, spacing * slot, garden, &mut state);
+                garden.num_steps += 1;
+
+                let done = runner.finish_step_with_state(garden, state, &mut stats[i]);
+                if done {
+                    copy_garden(&packed, spacing * slot, garden);
+                    finished.push(slot);
+                }
+                !done
+            });
+
+            // Clear finished gardens, so that these are skipped by subsequent steps
+            for slot in finished {
+                let masks = column_masks(&packed, spacing * slot..spacing * (slot + 1) - 1);
+                clear_columns(&mut packed, &masks);
+            }
+
+            if !running.is_empty() && running.len() * 2 <= num_packed {
+                for &(i, slot) in running.iter() {
+                    copy_garden(&packed, spacing * slot, &mut gardens[i]);
+                }
+                running.iter_mut().enumerate().for_each(|(slot, entry)| entry.1 = slot);
+                let repacked = pack(gardens, &running);
+                packed = repacked.0;
+                isolation_masks = repacked.1;
+                num_packed = running.len();
+            }
+        }
+
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::Transform;
+    use super::super::tests::random_cells;
+
+    // Returns a garden with a random 8x8 seed in its centre
+    fn new_garden(width: usize, height: usize, seed: u64) -> GameOfLife {
+        let mut garden = GameOfLife::new(width, height, false);
+
+        for (x, y) in random_cells(8, 8, seed, 0.5) {
+            garden.set(width / 2 - 4 + x, height / 2 - 4 + y);
+        }
+
+        garden
+    }
+
+    fn check_matches_single_runs(mut gardens: Vec<GameOfLife>, mut expected: Vec<GameOfLife>) {
+        let mut runner = GameOfLifeRunner::new(50, 1.0);
+        let expected_stats: Vec<RunStats> = expected.iter_mut().map(
+            |garden| runner.run(garden)
+        ).collect();
+
+        let stats = BatchRunner::new(50, 1.0).run(&mut gardens);
+
+        assert_eq!(stats, expected_stats);
+        for (garden, expected) in gardens.iter().zip(expected.iter()) {
+            assert_eq!(garden.to_bit_grid(), expected.to_bit_grid());
+            assert_eq!(garden.num_steps(), expected.num_steps());
+        }
+    }
+
+    #[test]
+    fn batch_matches_single_runs() {
+        for &(width, height) in [(16, 16), (32, 32), (64, 64), (70, 20)].iter() {
+            let gardens = (1..20).map(|seed| new_garden(width, height, seed)).collect();
+            let expected = (1..20).map(|seed| new_garden(width, height, seed)).collect();
+
+            check_matches_single_runs(gardens, expected);
+        }
+    }
+
+    #[test]
+    fn gardens_are_isolated() {
+        // Gliders that fly into the neighbouring garden, and a blinker against the border
+        let new_gardens = || {
+            let mut gardens = Vec::new();
+            for i in 0..3 {
+                let mut garden = GameOfLife::new(20, 10, false);
+                garden.place_pattern_result("glider", 16, 3 + i, Transform::Identity).unwrap();
+                garden.place_pattern_result("glider", 1, 3, Transform::FlipHorizontal).unwrap();
+                garden.place_pattern_result("blinker", 0, 0, Transform::Rotate90).unwrap();
+                gardens.push(garden);
+            }
+            gardens
+        };
+
+        check_matches_single_runs(new_gardens(), new_gardens());
+    }
+
+    #[test]
+    fn unpackable_gardens() {
+        let new_gardens = || vec![
+            new_garden(16, 16, 1), new_garden(20, 16, 2), GameOfLife::new(16, 16, true)
+        ];
+
+        check_matches_single_runs(new_gardens(), new_gardens());
+    }
+}
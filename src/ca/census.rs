@@ -0,0 +1,305 @@
+// Classification of the objects in a Game of Life state, similar to the census taken by
+// apgsearch. The state is split into islands of connected cells, which are identified by comparing
+// them against the still lifes, oscillators and spaceships of the pattern catalog. All phases and
+// all orientations of each object are recognised.
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use wasm_bindgen::prelude::*;
+use super::BitGrid;
+use super::patterns::{PatternKind, Transform, PATTERNS, TRANSFORMS};
+
+/// The name used for islands that do not match any known object
+pub const UNIDENTIFIED: &str = "other";
+
+// The maximum period of the objects in the catalog
+const MAX_PERIOD: usize = 30;
+
+pub(super) type Cell = (i32, i32);
+
+/// The number of objects of each type, by name
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Census {
+    counts: BTreeMap<&'static str, u32>,
+}
+
+/// Takes the census of a state. It holds the catalog of known objects, so that it only needs to
+/// be built once.
+pub struct CensusTaker {
+    // Maps the canonical form of each phase of each object to its name
+    catalog: HashMap<Vec<Cell>, &'static str>,
+}
+
+pub(super) fn parse_cells(pattern: &str) -> Vec<Cell> {
+    let mut cells = Vec::new();
+
+    for (y, line) in pattern.lines().enumerate() {
+        for (x, ch) in line.chars().enumerate() {
+            if ch == 'O' {
+                cells.push((x as i32, y as i32));
+            }
+        }
+    }
+
+    cells
+}
+
+// Evolves the cells by one generation on an unbounded grid
+pub(super) fn step_cells(cells: &[Cell]) -> Vec<Cell> {
+    let alive: HashSet<Cell> = cells.iter().copied().collect();
+    let mut counts: HashMap<Cell, usize> = HashMap::new();
+
+    for &(x, y) in cells {
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if dx != 0 || dy != 0 {
+                    *counts.entry((x + dx, y + dy)).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+
+    counts.into_iter().filter(
+        |(cell, count)| *count == 3 || (*count == 2 && alive.contains(cell))
+    ).map(|(cell, _)| cell).collect()
+}
+
+// Returns the cells in sorted order, translated so that their bounding box starts at the origin.
+pub(super) fn normalize(cells: &[Cell]) -> Vec<Cell> {
+    let x_min = cells.iter().map(|(x, _)| *x).min().unwrap_or(0);
+    let y_min = cells.iter().map(|(_, y)| *y).min().unwrap_or(0);
+
+    let mut normalized: Vec<Cell> = cells.iter().map(|(x, y)| (x - x_min, y - y_min)).collect();
+    normalized.sort_unstable();
+    normalized
+}
+
+// Returns the cells after applying the transform, translated so that their bounding box starts
+// at the origin.
+pub(super) fn transform_cells(cells: &[Cell], transform: Transform) -> Vec<Cell> {
+    let normalized = normalize(cells);
+    let width = normalized.iter().map(|(x, _)| *x as usize + 1).max().unwrap_or(0);
+    let height = normalized.iter().map(|(_, y)| *y as usize + 1).max().unwrap_or(0);
+
+    normalized.iter().map(|&(x, y)| {
+        let (tx, ty) = transform.apply(x as usize, y as usize, width, height);
+        (tx as i32, ty as i32)
+    }).collect()
+}
+
+// Returns a representation of the cells that does not depend on their position or orientation.
+// It is the smallest of the normalized cell lists of all eight symmetries of the square.
+fn canonical_form(cells: &[Cell]) -> Vec<Cell> {
+    TRANSFORMS.iter().map(
+        |&transform| normalize(&transform_cells(cells, transform))
+    ).min().unwrap()
+}
+
+// Splits the live cells into islands. Cells belong to the same island when they are within the
+// given (Chebyshev) distance of each other.
+pub(super) fn find_islands(cells: &[Cell], distance: i32) -> Vec<Vec<Cell>> {
+    let mut unvisited: HashSet<Cell> = cells.iter().copied().collect();
+    let mut islands = Vec::new();
+
+    for &start in cells {
+        if !unvisited.remove(&start) {
+            continue;
+        }
+
+        let mut island = vec![start];
+        let mut index = 0;
+        while index < island.len() {
+            let (x, y) = island[index];
+            for dy in -distance..=distance {
+                for dx in -distance..=distance {
+                    if unvisited.remove(&(x + dx, y + dy)) {
+                        island.push((x + dx, y + dy));
+                    }
+                }
+            }
+            index += 1;
+        }
+        islands.push(island);
+    }
+
+    islands
+}
+
+impl Census {
+    pub fn counts(&self) -> &BTreeMap<&'static str, u32> {
+        &self.counts
+    }
+
+    fn add(&mut self, name: &'static str) {
+        *self.counts.entry(name).or_insert(0) += 1;
+    }
+}
+
+#[wasm_bindgen]
+impl Census {
+    pub fn count(&self, name: &str) -> u32 {
+        self.counts.get(name).copied().unwrap_or(0)
+    }
+
+    pub fn num_objects(&self) -> u32 {
+        self.counts.values().sum()
+    }
+
+    // The number of different types of known objects. Unidentified islands are not included.
+    pub fn num_distinct(&self) -> u32 {
+        self.counts.keys().filter(|name| **name != UNIDENTIFIED).count() as u32
+    }
+
+    pub fn summary(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl fmt::Display for Census {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (name, count)) in self.counts.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} {}", count, name)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl CensusTaker {
+    pub fn new() -> Self {
+        let mut catalog = HashMap::new();
+
+        // Guns are excluded, as these do not return to their initial state
+        for pattern in PATTERNS.iter().filter(|pattern| pattern.kind != PatternKind::Gun) {
+            let name = pattern.name;
+            let mut cells = parse_cells(pattern.cells);
+            let initial = canonical_form(&cells);
+
+            for _ in 0..MAX_PERIOD {
+                catalog.insert(canonical_form(&cells), name);
+                cells = step_cells(&cells);
+                if canonical_form(&cells) == initial {
+                    break;
+                }
+            }
+        }
+
+        CensusTaker { catalog }
+    }
+
+    fn identify(&self, cells: &[Cell]) -> Option<&'static str> {
+        self.catalog.get(&canonical_form(cells)).copied()
+    }
+
+    /// Takes the census of the given state, assuming it evolves according to B3/S23.
+    ///
+    /// As some objects, like the pulsar, consist of separate parts, islands are formed from cells
+    /// that are at most two cells apart. Islands that cannot be identified are subsequently split
+    /// into parts that consist of cells that touch each other, as these may be separate objects
+    /// that happen to be close together.
+    pub fn take(&self, bit_grid: &BitGrid) -> Census {
+        let mut census = Census::default();
+
+        let mut cells = Vec::new();
+        for y in 0..bit_grid.height() {
+            for x in 0..bit_grid.width() {
+                if bit_grid.get(x, y) {
+                    cells.push((x as i32, y as i32));
+                }
+            }
+        }
+
+        for island in find_islands(&cells, 2) {
+            if let Some(name) = self.identify(&island) {
+                census.add(name);
+                continue;
+            }
+
+            for part in find_islands(&island, 1) {
+                census.add(self.identify(&part).unwrap_or(UNIDENTIFIED));
+            }
+        }
+
+        census
+    }
+}
+
+impl Default for CensusTaker {
+    fn default() -> Self {
+        CensusTaker::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paste(bit_grid: &mut BitGrid, pattern: &str, x0: usize, y0: usize) {
+        for (x, y) in parse_cells(pattern) {
+            bit_grid.set(x0 + x as usize, y0 + y as usize);
+        }
+    }
+
+    #[test]
+    fn catalog_phases() {
+        let census_taker = CensusTaker::new();
+        let num_phases = |name| census_taker.catalog.values().filter(|n| **n == name).count();
+
+        assert_eq!(num_phases("block"), 1);
+        assert_eq!(num_phases("blinker"), 1);
+        assert_eq!(num_phases("beacon"), 2);
+        assert_eq!(num_phases("pulsar"), 3);
+        assert_eq!(num_phases("pentadecathlon"), 15);
+        // Two phases of the glider are mirror images of the other two
+        assert_eq!(num_phases("glider"), 2);
+    }
+
+    #[test]
+    fn identify_objects() {
+        let mut bit_grid = BitGrid::new(40, 30);
+        paste(&mut bit_grid, "OO\nOO", 1, 1);
+        paste(&mut bit_grid, "OO\nOO", 6, 1);
+        paste(&mut bit_grid, "O\nO\nO", 12, 1);
+        // Rotated and mirrored boat
+        paste(&mut bit_grid, ".O.\nO.O\n.OO", 16, 1);
+        // Glider heading north-west
+        paste(&mut bit_grid, "OOO\nO..\n.O.", 22, 1);
+        let pulsar = super::super::patterns::find("pulsar").unwrap();
+        paste(&mut bit_grid, pulsar.cells, 1, 8);
+        // Beacon in its six-cell phase, consisting of two separate parts
+        paste(&mut bit_grid, "OO..\nO...\n...O\n..OO", 20, 10);
+        // Block and blinker that are close together
+        paste(&mut bit_grid, "OO\nOO", 30, 5);
+        paste(&mut bit_grid, "O\nO\nO", 33, 5);
+        // Unknown objects
+        paste(&mut bit_grid, "O", 30, 20);
+        paste(&mut bit_grid, "OOOO", 30, 25);
+
+        let census = CensusTaker::new().take(&bit_grid);
+
+        assert_eq!(census.count("block"), 3);
+        assert_eq!(census.count("blinker"), 2);
+        assert_eq!(census.count("boat"), 1);
+        assert_eq!(census.count("glider"), 1);
+        assert_eq!(census.count("pulsar"), 1);
+        assert_eq!(census.count("beacon"), 1);
+        assert_eq!(census.count(UNIDENTIFIED), 2);
+        assert_eq!(census.num_objects(), 11);
+        assert_eq!(census.num_distinct(), 6);
+        assert_eq!(
+            census.summary(),
+            "1 beacon, 2 blinker, 3 block, 1 boat, 1 glider, 2 other, 1 pulsar"
+        );
+    }
+
+    #[test]
+    fn empty_state() {
+        let census = CensusTaker::new().take(&BitGrid::new(10, 10));
+
+        assert_eq!(census.num_objects(), 0);
+        assert_eq!(census.summary(), "");
+    }
+}
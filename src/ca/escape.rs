@@ -0,0 +1,139 @@
+// Detection of spaceships that are about to leave the grid, as used for GridBorder::Absorbing.
+// Without it, spaceships that reach a border of zeroes collide with it and turn into debris.
+use std::collections::{HashMap, HashSet};
+use super::census::{normalize, parse_cells, step_cells, transform_cells, Cell};
+use super::patterns::{PatternKind, PATTERNS, TRANSFORMS};
+use super::{GameOfLife, RunStats};
+
+// Spaceships are removed as soon as one of their cells is within this distance of the border. At
+// that moment they have not yet been affected by the border.
+const MARGIN: usize = 2;
+
+// The maximum number of cells of the spaceships that are detected
+const MAX_SPACESHIP_CELLS: usize = 32;
+
+// The maximum period of the spaceships that are detected
+const MAX_PERIOD: usize = 8;
+
+pub(super) struct EscapeDetector {
+    // Maps each phase of each spaceship, in each orientation, to its direction of travel
+    phases: HashMap<Vec<Cell>, (i32, i32)>,
+}
+
+impl EscapeDetector {
+    pub fn new() -> Self {
+        let mut phases = HashMap::new();
+
+        for pattern in PATTERNS.iter().filter(|pattern| pattern.kind == PatternKind::Spaceship) {
+            for &transform in TRANSFORMS.iter() {
+                let initial = transform_cells(&parse_cells(pattern.cells), transform);
+                let normalized_initial = normalize(&initial);
+
+                let mut cells = initial.clone();
+                let mut visited = Vec::new();
+                for _ in 0..MAX_PERIOD {
+                    visited.push(normalize(&cells));
+                    cells = step_cells(&cells);
+
+                    if normalize(&cells) == normalized_initial {
+                        let x_min = |cells: &[Cell]| cells.iter().map(|(x, _)| *x).min().unwrap();
+                        let y_min = |cells: &[Cell]| cells.iter().map(|(_, y)| *y).min().unwrap();
+                        let dx = x_min(&cells) - x_min(&initial);
+                        let dy = y_min(&cells) - y_min(&initial);
+                        for phase in visited.drain(..) {
+                            phases.insert(phase, (dx.signum(), dy.signum()));
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+
+        EscapeDetector { phases }
+    }
+
+    // Returns the cells of the island that contains the given cell. Cells belong to the same
+    // island when they are at most two cells apart. Returns None when the island is too large to
+    // be a spaceship.
+    fn find_island(
+        &self, gol: &GameOfLife, x: usize, y: usize, visited: &mut HashSet<(usize, usize)>
+    ) -> Option<Vec<Cell>> {
+        let mut island = vec![(x, y)];
+        visited.insert((x, y));
+
+        let mut index = 0;
+        while index < island.len() {
+            if island.len() > MAX_SPACESHIP_CELLS {
+                return None;
+            }
+
+            let (x, y) = island[index];
+            for ny in y.saturating_sub(2)..(y + 3).min(gol.height()) {
+                for nx in x.saturating_sub(2)..(x + 3).min(gol.width()) {
+                    if gol.get(nx, ny) && visited.insert((nx, ny)) {
+                        island.push((nx, ny));
+                    }
+                }
+            }
+            index += 1;
+        }
+
+        Some(island.iter().map(|&(x, y)| (x as i32, y as i32)).collect())
+    }
+
+    // Removes the spaceship that contains the given cell, if it is moving out of the grid.
+    fn remove_if_escaping(
+        &self, gol: &mut GameOfLife, x: usize, y: usize, visited: &mut HashSet<(usize, usize)>,
+        stats: &mut RunStats
+    ) {
+        let island = match self.find_island(gol, x, y, visited) {
+            Some(island) => island,
+            None => return,
+        };
+        let (dx, dy) = match self.phases.get(&normalize(&island)) {
+            Some(&direction) => direction,
+            None => return,
+        };
+
+        let margin = MARGIN as i32;
+        let x_min = island.iter().map(|(x, _)| *x).min().unwrap();
+        let x_max = island.iter().map(|(x, _)| *x).max().unwrap();
+        let y_min = island.iter().map(|(_, y)| *y).min().unwrap();
+        let y_max = island.iter().map(|(_, y)| *y).max().unwrap();
+        let escaping = (dx < 0 && x_min < margin)
+            || (dx > 0 && x_max + margin >= gol.width() as i32)
+            || (dy < 0 && y_min < margin)
+            || (dy > 0 && y_max + margin >= gol.height() as i32);
+
+        if escaping {
+            for (x, y) in island {
+                gol.clear(x as usize, y as usize);
+            }
+            stats.record_escape(dx, dy);
+        }
+    }
+
+    // Removes all spaceships that are about to leave the grid, and records these in the stats.
+    pub fn remove_escaping_spaceships(&self, gol: &mut GameOfLife, stats: &mut RunStats) {
+        let mut visited = HashSet::new();
+        let (width, height) = (gol.width(), gol.height());
+
+        for y in 0..height {
+            let in_band = y < MARGIN || y + MARGIN >= height;
+            let mut x = 0;
+
+            while x < width {
+                if gol.get(x, y) && !visited.contains(&(x, y)) {
+                    self.remove_if_escaping(gol, x, y, &mut visited, stats);
+                }
+
+                // Away from the top and bottom, only visit the cells near the left and right
+                if !in_band && x + 1 == MARGIN && width > 2 * MARGIN {
+                    x = width - MARGIN;
+                } else {
+                    x += 1;
+                }
+            }
+        }
+    }
+}
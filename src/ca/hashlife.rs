@@ -0,0 +1,481 @@
+// A HashLife engine, based on Bill Gosper's algorithm. The grid is represented by a quadtree whose
+// nodes are canonicalized, so that identical sub-patterns share the same node. The evolution of
+// each node is memoized, which allows the engine to jump 2^k generations at once.
+//
+// To match the behaviour of GridBorder::Zeroes, the grid is surrounded by wall cells. These never
+// change and never count as live neighbours, so cells outside the grid remain dead forever. As
+// walls are part of the state, nodes remain independent of their position, and memoization keeps
+// working near the border.
+//
+// Rules with births on zero neighbours (B0) are not supported, as empty regions are assumed to
+// stay empty.
+use std::collections::HashMap;
+use super::{GameOfLife, Rule};
+
+type NodeId = u32;
+
+const DEAD: NodeId = 0;
+const ALIVE: NodeId = 1;
+const WALL: NodeId = 2;
+
+// When the number of nodes exceeds this limit, nodes that are not used by the current state are
+// discarded, together with all memoized results.
+const MAX_NODES: usize = 1 << 22;
+
+struct Node {
+    level: u8,
+    // Children in order: NW, NE, SW, SE. Unused for leaves (level zero).
+    children: [NodeId; 4],
+    population: u64,
+}
+
+pub struct HashLife {
+    width: usize,
+    height: usize,
+    rule: Rule,
+
+    nodes: Vec<Node>,
+    index: HashMap<[NodeId; 4], NodeId>,
+    results: HashMap<(NodeId, u8), NodeId>,
+    // Nodes of each level that only contain wall cells
+    walls: Vec<NodeId>,
+
+    // Node whose top-left corner contains the grid. All other cells are walls.
+    core: NodeId,
+    num_steps: u64,
+}
+
+impl HashLife {
+    pub fn new(width: usize, height: usize, rule: Rule) -> Self {
+        let mut hashlife = HashLife {
+            width,
+            height,
+            rule,
+            nodes: Vec::new(),
+            index: HashMap::new(),
+            results: HashMap::new(),
+            walls: Vec::new(),
+            core: WALL,
+            num_steps: 0,
+        };
+        hashlife.init_leaves();
+        hashlife.core = hashlife.build(0, 0, hashlife.core_level(), &|_, _| false);
+
+        hashlife
+    }
+
+    pub fn from_game_of_life(gol: &GameOfLife) -> Self {
+        let mut hashlife = HashLife::new(gol.width(), gol.height(), *gol.rule());
+        hashlife.core = hashlife.build(0, 0, hashlife.core_level(), &|x, y| gol.get(x, y));
+
+        hashlife
+    }
+
+    fn init_leaves(&mut self) {
+        self.nodes.clear();
+        self.index.clear();
+        self.results.clear();
+
+        for &population in [0, 1, 0].iter() {
+            self.nodes.push(Node { level: 0, children: [DEAD; 4], population });
+        }
+        self.walls = vec![WALL];
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn num_steps(&self) -> u64 {
+        self.num_steps
+    }
+
+    pub fn population(&self) -> u64 {
+        self.nodes[self.core as usize].population
+    }
+
+    // The level of the smallest node that can contain the grid
+    fn core_level(&self) -> u8 {
+        let size = self.width.max(self.height).max(2);
+        (usize::BITS - (size - 1).leading_zeros()) as u8
+    }
+
+    fn join(&mut self, children: [NodeId; 4]) -> NodeId {
+        if let Some(&id) = self.index.get(&children) {
+            return id;
+        }
+
+        let id = self.nodes.len() as NodeId;
+        self.nodes.push(Node {
+            level: self.nodes[children[0] as usize].level + 1,
+            children,
+            population: children.iter().map(|&c| self.nodes[c as usize].population).sum(),
+        });
+        self.index.insert(children, id);
+
+        id
+    }
+
+    fn wall(&mut self, level: u8) -> NodeId {
+        while self.walls.len() <= level as usize {
+            let w = *self.walls.last().unwrap();
+            let node = self.join([w, w, w, w]);
+            self.walls.push(node);
+        }
+
+        self.walls[level as usize]
+    }
+
+    fn child(&self, node: NodeId, index: usize) -> NodeId {
+        self.nodes[node as usize].children[index]
+    }
+
+    // Builds the node of the given level whose top-left corner is at the given grid position.
+    fn build(
+        &mut self, x0: usize, y0: usize, level: u8, is_alive: &dyn Fn(usize, usize) -> bool
+    ) -> NodeId {
+        if x0 >= self.width || y0 >= self.height {
+            return self.wall(level);
+        }
+        if level == 0 {
+            return if is_alive(x0, y0) { ALIVE } else { DEAD };
+        }
+
+        let half = 1 << (level - 1);
+        let nw = self.build(x0, y0, level - 1, is_alive);
+        let ne = self.build(x0 + half, y0, level - 1, is_alive);
+        let sw = self.build(x0, y0 + half, level - 1, is_alive);
+        let se = self.build(x0 + half, y0 + half, level - 1, is_alive);
+
+        self.join([nw, ne, sw, se])
+    }
+
+    // Returns the node one level higher, with the given node at its center, surrounded by walls.
+    fn expand(&mut self, node: NodeId) -> NodeId {
+        let level = self.nodes[node as usize].level;
+        let w = self.wall(level - 1);
+        let [nw, ne, sw, se] = self.nodes[node as usize].children;
+
+        let nw = self.join([w, w, w, nw]);
+        let ne = self.join([w, w, ne, w]);
+        let sw = self.join([w, sw, w, w]);
+        let se = self.join([se, w, w, w]);
+        self.join([nw, ne, sw, se])
+    }
+
+    // Returns the node one level lower at the center of the given node.
+    fn center(&mut self, node: NodeId) -> NodeId {
+        let [nw, ne, sw, se] = self.nodes[node as usize].children;
+        let children = [self.child(nw, 3), self.child(ne, 2), self.child(sw, 1), self.child(se, 0)];
+
+        self.join(children)
+    }
+
+    // Evolves the 2x2 center of a 4x4 node by one generation.
+    fn evolve_base(&mut self, node: NodeId) -> NodeId {
+        let mut cells = [[DEAD; 4]; 4];
+        for (quadrant, &child) in self.nodes[node as usize].children.iter().enumerate() {
+            for (i, &leaf) in self.nodes[child as usize].children.iter().enumerate() {
+                let x = (quadrant % 2) * 2 + i % 2;
+                let y = (quadrant / 2) * 2 + i / 2;
+                cells[y][x] = leaf;
+            }
+        }
+
+        let mut result = [DEAD; 4];
+        for (i, cell) in result.iter_mut().enumerate() {
+            let x = 1 + i % 2;
+            let y = 1 + i / 2;
+            *cell = if cells[y][x] == WALL {
+                WALL
+            } else {
+                let neighbourhood = cells[y - 1..=y + 1].iter().flat_map(
+                    |row| row[x - 1..=x + 1].iter()
+                ).filter(|&&leaf| leaf == ALIVE).count();
+                let count = neighbourhood - if cells[y][x] == ALIVE { 1 } else { 0 };
+
+                let alive = if cells[y][x] == ALIVE {
+                    self.rule.survives_on(count)
+                } else {
+                    self.rule.births_on(count)
+                };
+                if alive { ALIVE } else { DEAD }
+            };
+        }
+
+        self.join(result)
+    }
+
+    // Returns the center of the node, one level lower, after 2^j generations. Requires that j is
+    // at most the level of the node minus two.
+    fn next(&mut self, node: NodeId, j: u8) -> NodeId {
+        if let Some(&result) = self.results.get(&(node, j)) {
+            return result;
+        }
+
+        let level = self.nodes[node as usize].level;
+        debug_assert!(j + 2 <= level);
+
+        let result = if self.nodes[node as usize].population == 0 {
+            // Without live cells nothing changes
+            self.center(node)
+        } else if level == 2 {
+            self.evolve_base(node)
+        } else {
+            let [nw, ne, sw, se] = self.nodes[node as usize].children;
+            let [_, nw_ne, nw_sw, nw_se] = self.nodes[nw as usize].children;
+            let [ne_nw, _, ne_sw, ne_se] = self.nodes[ne as usize].children;
+            let [sw_nw, sw_ne, _, sw_se] = self.nodes[sw as usize].children;
+            let [se_nw, se_ne, se_sw, _] = self.nodes[se as usize].children;
+
+            // The nine overlapping sub-nodes, one level lower
+            let n01 = self.join([nw_ne, ne_nw, nw_se, ne_sw]);
+            let n10 = self.join([nw_sw, nw_se, sw_nw, sw_ne]);
+            let n11 = self.join([nw_se, ne_sw, sw_ne, se_nw]);
+            let n12 = self.join([ne_sw, ne_se, se_nw, se_ne]);
+            let n21 = self.join([sw_ne, se_nw, sw_se, se_sw]);
+            let sub_nodes = [nw, n01, ne, n10, n11, n12, sw, n21, se];
+
+            // When jumping the maximum number of generations, both stages advance the pattern.
+            // Otherwise, only the second stage does.
+            let full_speed = j + 2 == level;
+            let mut r = [DEAD; 9];
+            for (i, &sub_node) in sub_nodes.iter().enumerate() {
+                r[i] = if full_speed { self.next(sub_node, j - 1) } else { self.center(sub_node) };
+            }
+
+            let j2 = if full_speed { j - 1 } else { j };
+            let nw = self.join([r[0], r[1], r[3], r[4]]);
+            let ne = self.join([r[1], r[2], r[4], r[5]]);
+            let sw = self.join([r[3], r[4], r[6], r[7]]);
+            let se = self.join([r[4], r[5], r[7], r[8]]);
+            let nw = self.next(nw, j2);
+            let ne = self.next(ne, j2);
+            let sw = self.next(sw, j2);
+            let se = self.next(se, j2);
+            self.join([nw, ne, sw, se])
+        };
+
+        self.results.insert((node, j), result);
+        result
+    }
+
+    /// Advances the grid by 2^k generations.
+    pub fn step_pow2(&mut self, k: u8) {
+        let core_level = self.nodes[self.core as usize].level;
+
+        // Surround the core by walls until the root is large enough for the jump
+        let mut root = self.expand(self.core);
+        while self.nodes[root as usize].level < k + 2 {
+            root = self.expand(root);
+        }
+
+        let mut result = self.next(root, k);
+        while self.nodes[result as usize].level > core_level {
+            result = self.center(result);
+        }
+
+        self.core = result;
+        self.num_steps += 1 << k;
+
+        if self.nodes.len() > MAX_NODES {
+            self.collect_garbage();
+        }
+    }
+
+    /// Advances the grid by the given number of generations.
+    pub fn step(&mut self, num_steps: u64) {
+        for k in (0..64).rev() {
+            if (num_steps >> k) & 1 == 1 {
+                self.step_pow2(k);
+            }
+        }
+    }
+
+    // Discards all nodes and results that are not needed to represent the current state.
+    fn collect_garbage(&mut self) {
+        let old_nodes = std::mem::take(&mut self.nodes);
+        self.init_leaves();
+
+        let mut mapping = HashMap::new();
+        self.core = self.copy_node(&old_nodes, self.core, &mut mapping);
+    }
+
+    fn copy_node(
+        &mut self, old_nodes: &[Node], node: NodeId, mapping: &mut HashMap<NodeId, NodeId>
+    ) -> NodeId {
+        if old_nodes[node as usize].level == 0 {
+            return node;
+        }
+        if let Some(&id) = mapping.get(&node) {
+            return id;
+        }
+
+        let mut children = old_nodes[node as usize].children;
+        for child in children.iter_mut() {
+            *child = self.copy_node(old_nodes, *child, mapping);
+        }
+        let id = self.join(children);
+        mapping.insert(node, id);
+
+        id
+    }
+
+    fn visit_live_cells(
+        &self, node: NodeId, x0: usize, y0: usize, visit: &mut dyn FnMut(usize, usize)
+    ) {
+        let n = &self.nodes[node as usize];
+        if n.population == 0 {
+            return;
+        }
+        if n.level == 0 {
+            visit(x0, y0);
+            return;
+        }
+
+        let half = 1 << (n.level - 1);
+        for (i, &child) in n.children.iter().enumerate() {
+            self.visit_live_cells(child, x0 + (i % 2) * half, y0 + (i / 2) * half, visit);
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        let mut node = self.core;
+        let mut level = self.nodes[node as usize].level;
+
+        while level > 0 {
+            let half = 1 << (level - 1);
+            let index = if x & half != 0 { 1 } else { 0 } + if y & half != 0 { 2 } else { 0 };
+            node = self.child(node, index);
+            level -= 1;
+        }
+
+        node == ALIVE
+    }
+
+    /// Copies the state to the given Game of Life, which should have the same size.
+    pub fn write_to(&self, gol: &mut GameOfLife) {
+        assert_eq!(gol.width(), self.width);
+        assert_eq!(gol.height(), self.height);
+
+        let num_steps = gol.num_steps();
+        gol.reset();
+        gol.num_steps = num_steps;
+
+        self.visit_live_cells(self.core, 0, 0, &mut |x, y| gol.set(x, y));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::BitCounter;
+
+    fn add_r_pentomino(gol: &mut GameOfLife, x: usize, y: usize) {
+        // R-pentomino pattern:
+        //    * *
+        //  * *
+        //    *
+        gol.set(x + 1, y);
+        gol.set(x + 2, y);
+        gol.set(x, y + 1);
+        gol.set(x + 1, y + 1);
+        gol.set(x + 1, y + 2);
+    }
+
+    fn assert_same_state(hashlife: &HashLife, gol: &GameOfLife) {
+        for y in 0..gol.height() {
+            for x in 0..gol.width() {
+                assert_eq!(hashlife.get(x, y), gol.get(x, y), "Mismatch at ({}, {})", x, y);
+            }
+        }
+        assert_eq!(hashlife.population() as usize, BitCounter::new().count_live_cells(gol));
+    }
+
+    fn compare_with_bit_parallel_step(width: usize, height: usize, steps: &[u64]) {
+        let mut gol = GameOfLife::new(width, height, false);
+        add_r_pentomino(&mut gol, width / 2, height / 3);
+        add_r_pentomino(&mut gol, width - 4, 1);
+
+        let mut hashlife = HashLife::from_game_of_life(&gol);
+        assert_same_state(&hashlife, &gol);
+
+        for &n in steps {
+            hashlife.step(n);
+            for _ in 0..n {
+                gol.step();
+            }
+            assert_same_state(&hashlife, &gol);
+        }
+        assert_eq!(hashlife.num_steps(), gol.num_steps() as u64);
+    }
+
+    #[test]
+    fn matches_bit_parallel_step_square() {
+        compare_with_bit_parallel_step(32, 32, &[1, 1, 2, 7, 16, 100, 333]);
+    }
+
+    #[test]
+    fn matches_bit_parallel_step_non_square() {
+        compare_with_bit_parallel_step(70, 23, &[1, 5, 64, 128, 300]);
+    }
+
+    #[test]
+    fn glider_becomes_block_at_border() {
+        let mut gol = GameOfLife::new(12, 10, false);
+        gol.set(1, 0);
+        gol.set(2, 1);
+        gol.set(0, 2);
+        gol.set(1, 2);
+        gol.set(2, 2);
+
+        let mut hashlife = HashLife::from_game_of_life(&gol);
+        hashlife.step(1 << 10);
+        for _ in 0..1 << 10 {
+            gol.step();
+        }
+
+        assert_eq!(hashlife.population(), 4);
+        assert_same_state(&hashlife, &gol);
+
+        let mut copy = GameOfLife::new(12, 10, false);
+        hashlife.write_to(&mut copy);
+        assert_eq!(copy.to_bit_grid(), gol.to_bit_grid());
+    }
+
+    #[test]
+    fn huge_jump() {
+        let mut gol = GameOfLife::new(64, 64, false);
+        add_r_pentomino(&mut gol, 30, 30);
+        let mut hashlife = HashLife::from_game_of_life(&gol);
+
+        hashlife.step_pow2(40);
+
+        assert_eq!(hashlife.num_steps(), 1 << 40);
+        // The R-pentomino settles long before that, so another jump should not change anything
+        let population = hashlife.population();
+        hashlife.step_pow2(3);
+        hashlife.step_pow2(3);
+        assert_eq!(hashlife.population(), population);
+    }
+
+    #[test]
+    fn garbage_collection_preserves_state() {
+        let mut gol = GameOfLife::new(40, 40, false);
+        add_r_pentomino(&mut gol, 20, 20);
+
+        let mut hashlife = HashLife::from_game_of_life(&gol);
+        hashlife.step(50);
+        hashlife.collect_garbage();
+        hashlife.step(50);
+
+        for _ in 0..100 {
+            gol.step();
+        }
+        assert_same_state(&hashlife, &gol);
+    }
+}
@@ -0,0 +1,275 @@
+// A two-colour variant of a Life-like CA, known as Immigration for B3/S23. Each live cell is owned
+// by one of two players. Whether cells are alive evolves exactly as in the single-colour CA. A
+// surviving cell keeps its owner, and a cell that is born is owned by the player that owns the
+// majority of its live neighbours.
+//
+// The live cells and the cells of the second player are each stored in a GameOfLife. The latter
+// is stepped with a rule that sets every cell with a majority of second-player neighbours, after
+// which it is combined with the live cells. This way, both colours are updated with bitwise
+// operations only.
+use wasm_bindgen::prelude::*;
+use super::{BitCounter, BitGrid, GameOfLife, GridBorder, Rule, UnitType};
+
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Player {
+    First,
+    Second,
+}
+
+#[wasm_bindgen]
+pub struct Immigration {
+    alive: GameOfLife,
+    // The cells owned by the second player. These are always a subset of the live cells.
+    second: GameOfLife,
+    // The states of both grids before the last step
+    prev_alive: Vec<UnitType>,
+    prev_second: Vec<UnitType>,
+}
+
+// Returns the rule that sets exactly those cells whose new owner would be the second player if
+// they were born, i.e. the cells where the second player owns a majority of the parents.
+fn majority_rule(rule: &Rule) -> Result<Rule, String> {
+    let births: Vec<usize> = (0..=8).filter(|&n| rule.births_on(n)).collect();
+    if births.len() != 1 || births[0] == 0 {
+        return Err(format!(
+            "Rule {} is not supported by the two-colour CA, as it requires a single birth count \
+             of at least one", rule
+        ));
+    }
+
+    // Ties, which can only occur for even birth counts, go to the first player
+    let counts: Vec<usize> = (births[0] / 2 + 1..=8).collect();
+    Rule::new(&counts, &counts)
+}
+
+impl Immigration {
+    pub fn new_result(
+        width: usize, height: usize, border: GridBorder, rule: Rule
+    ) -> Result<Self, String> {
+        if border == GridBorder::Alive {
+            return Err("Border of live cells is not supported by the two-colour CA".to_string());
+        }
+
+        let mut alive = GameOfLife::new_result(width, height, border)?;
+        alive.set_rule(rule);
+        let mut second = GameOfLife::new_result(width, height, border)?;
+        second.set_rule(majority_rule(&rule)?);
+
+        Ok(Immigration {
+            alive,
+            second,
+            prev_alive: Vec::new(),
+            prev_second: Vec::new(),
+        })
+    }
+
+    /// Returns the owner of the cell, or None when it is dead.
+    pub fn get(&self, x: usize, y: usize) -> Option<Player> {
+        if !self.alive.get(x, y) {
+            None
+        } else if self.second.get(x, y) {
+            Some(Player::Second)
+        } else {
+            Some(Player::First)
+        }
+    }
+
+    /// Places the live cells of the grid with its top-left corner at the given position. These
+    /// cells become owned by the player. Cells that are dead in the grid are left unchanged.
+    pub fn place_result(
+        &mut self, bit_grid: &BitGrid, x0: usize, y0: usize, player: Player
+    ) -> Result<(), String> {
+        self.alive.paste(bit_grid, x0, y0)?;
+
+        for y in 0..bit_grid.height() {
+            for x in 0..bit_grid.width() {
+                if bit_grid.get(x, y) {
+                    self.set(x0 + x, y0 + y, player);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The live cells, irrespective of their owner.
+    pub fn alive(&self) -> &GameOfLife {
+        &self.alive
+    }
+}
+
+#[wasm_bindgen]
+impl Immigration {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        width: usize, height: usize, border: GridBorder, rulestring: &str
+    ) -> Result<Immigration, JsValue> {
+        rulestring.parse().and_then(
+            |rule| Immigration::new_result(width, height, border, rule)
+        ).map_err(|msg| JsValue::from_str(&msg))
+    }
+
+    pub fn width(&self) -> usize {
+        self.alive.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.alive.height()
+    }
+
+    pub fn num_steps(&self) -> u32 {
+        self.alive.num_steps()
+    }
+
+    pub fn reset(&mut self) {
+        self.alive.reset();
+        self.second.reset();
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, player: Player) {
+        self.alive.set(x, y);
+        match player {
+            Player::First => self.second.clear(x, y),
+            Player::Second => self.second.set(x, y),
+        }
+    }
+
+    pub fn clear(&mut self, x: usize, y: usize) {
+        self.alive.clear(x, y);
+        self.second.clear(x, y);
+    }
+
+    pub fn place(
+        &mut self, bit_grid: &BitGrid, x0: usize, y0: usize, player: Player
+    ) -> Result<(), JsValue> {
+        self.place_result(bit_grid, x0, y0, player).map_err(|msg| JsValue::from_str(&msg))
+    }
+
+    pub fn step(&mut self) {
+        self.prev_alive.clone_from(&self.alive.bit_grid.units);
+        self.prev_second.clone_from(&self.second.bit_grid.units);
+
+        self.alive.step();
+        self.second.step();
+
+        for (((second, alive), prev_alive), prev_second) in self.second.bit_grid.units.iter_mut()
+            .zip(self.alive.bit_grid.units.iter())
+            .zip(self.prev_alive.iter())
+            .zip(self.prev_second.iter())
+        {
+            // Survivors keep their owner. Births go to the player with the majority
+            *second = alive & (prev_second | (!prev_alive & *second));
+        }
+    }
+
+    /// Returns the number of live cells owned by the player.
+    pub fn num_cells(&self, player: Player) -> usize {
+        let bit_counter = BitCounter::new();
+        let num_second = bit_counter.count_live_cells(&self.second);
+
+        match player {
+            Player::First => bit_counter.count_live_cells(&self.alive) - num_second,
+            Player::Second => num_second,
+        }
+    }
+
+    /// Returns the cells owned by the player.
+    pub fn to_bit_grid(&self, player: Player) -> BitGrid {
+        let mut bit_grid = self.alive.to_bit_grid();
+        let second = self.second.to_bit_grid();
+
+        match player {
+            Player::First => bit_grid.and_not(&second),
+            Player::Second => bit_grid.and(&second),
+        }
+
+        bit_grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::Transform;
+    use super::super::tests::random_cells;
+
+    fn new_immigration(border: GridBorder, rule: &str) -> Immigration {
+        Immigration::new_result(32, 24, border, rule.parse().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn live_cells_evolve_as_single_colour_ca() {
+        for &border in [GridBorder::Zeroes, GridBorder::Wrapped, GridBorder::KleinBottle].iter() {
+            for rule in ["B3/S23", "B2/S", "B4/S34"].iter() {
+                let mut immigration = new_immigration(border, rule);
+                let mut gol = GameOfLife::new_result(32, 24, border).unwrap();
+                gol.set_rule(rule.parse().unwrap());
+
+                for (x, y) in random_cells(32, 24, 12345, 1.0 / 3.0) {
+                    gol.set(x, y);
+                    let player = if x < 16 { Player::First } else { Player::Second };
+                    immigration.set(x, y, player);
+                }
+
+                for _ in 0..50 {
+                    gol.step();
+                    immigration.step();
+                    assert_eq!(immigration.alive().to_bit_grid(), gol.to_bit_grid());
+
+                    let mut owned = immigration.to_bit_grid(Player::First);
+                    let second = immigration.to_bit_grid(Player::Second);
+                    let mut overlap = owned.clone();
+                    overlap.and(&second);
+                    assert_eq!(overlap, BitGrid::new(32, 24));
+                    owned.or(&second);
+                    assert_eq!(owned, gol.to_bit_grid(), "{:?} {}", border, rule);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn births_go_to_majority() {
+        let mut immigration = new_immigration(GridBorder::Zeroes, "B3/S23");
+        immigration.set(1, 1, Player::Second);
+        immigration.set(2, 1, Player::First);
+        immigration.set(3, 1, Player::Second);
+
+        immigration.step();
+
+        assert_eq!(immigration.get(2, 0), Some(Player::Second));
+        assert_eq!(immigration.get(2, 1), Some(Player::First));
+        assert_eq!(immigration.get(2, 2), Some(Player::Second));
+        assert_eq!(immigration.get(1, 1), None);
+        assert_eq!(immigration.num_cells(Player::First), 1);
+        assert_eq!(immigration.num_cells(Player::Second), 2);
+    }
+
+    #[test]
+    fn single_player_keeps_all_cells() {
+        for &player in [Player::First, Player::Second].iter() {
+            let mut immigration = new_immigration(GridBorder::Wrapped, "B3/S23");
+            let glider = super::super::patterns::find("glider").unwrap();
+            immigration.place_result(&glider.to_bit_grid(Transform::Identity), 29, 21, player)
+                .unwrap();
+
+            for _ in 0..40 {
+                immigration.step();
+                assert_eq!(immigration.num_cells(player), 5);
+            }
+        }
+    }
+
+    #[test]
+    fn unsupported_settings() {
+        let new_result = |border, rule: &str| {
+            Immigration::new_result(16, 16, border, rule.parse().unwrap()).map(|_| ())
+        };
+
+        assert!(new_result(GridBorder::Zeroes, "B36/S23").is_err());
+        assert!(new_result(GridBorder::Zeroes, "B0/S8").is_err());
+        assert!(new_result(GridBorder::Alive, "B3/S23").is_err());
+        assert!(new_result(GridBorder::Mirror, "B2/S").is_ok());
+    }
+}
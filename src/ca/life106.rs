@@ -0,0 +1,140 @@
+// Support for the Life 1.06 pattern format, which lists the coordinates of all live cells.
+// See: https://conwaylife.com/wiki/Life_1.06
+use std::convert::TryFrom;
+use std::fmt::Write;
+use super::{check_pattern_size, parse_error, BitGrid};
+
+const HEADER: &str = "#Life 1.06";
+
+// Splits the line into whitespace-separated tokens, each together with the column it starts at.
+fn tokenize(line: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+
+    for (i, ch) in line.char_indices() {
+        if ch.is_whitespace() {
+            if let Some(s) = start.take() {
+                tokens.push((s, &line[s..i]));
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+    if let Some(s) = start {
+        tokens.push((s, &line[s..]));
+    }
+
+    tokens
+}
+
+fn parse_coordinate(line_no: usize, (column, token): (usize, &str)) -> Result<i64, String> {
+    token.parse::<i64>().map_err(
+        |_| parse_error(line_no, column, &format!("Invalid coordinate '{}'", token))
+    )
+}
+
+// Returns the number of cells from min to max, inclusive
+fn extent(min: i64, max: i64) -> Result<usize, String> {
+    max.checked_sub(min).and_then(|d| d.checked_add(1)).and_then(|n| usize::try_from(n).ok())
+        .ok_or_else(|| format!("Pattern spans too many cells from {} to {}", min, max))
+}
+
+/// Parses a pattern in Life 1.06 format. As coordinates can be negative, the returned pattern is
+/// the bounding box of all live cells.
+pub fn parse(text: &str) -> Result<BitGrid, String> {
+    let mut cells = Vec::new();
+
+    for (line_no, line) in text.lines().enumerate() {
+        let tokens = tokenize(line);
+        match tokens.first() {
+            // Skip empty lines, the header and other comments
+            None => continue,
+            Some((_, token)) if token.starts_with('#') => continue,
+            Some((column, _)) if tokens.len() != 2 => {
+                return Err(parse_error(line_no, *column, "Expected two coordinates"));
+            },
+            _ => {}
+        }
+
+        let x = parse_coordinate(line_no, tokens[0])?;
+        let y = parse_coordinate(line_no, tokens[1])?;
+        cells.push((x, y));
+    }
+
+    let x_min = cells.iter().map(|(x, _)| *x).min().unwrap_or(0);
+    let x_max = cells.iter().map(|(x, _)| *x).max().unwrap_or(-1);
+    let y_min = cells.iter().map(|(_, y)| *y).min().unwrap_or(0);
+    let y_max = cells.iter().map(|(_, y)| *y).max().unwrap_or(-1);
+
+    let width = extent(x_min, x_max)?;
+    let height = extent(y_min, y_max)?;
+    check_pattern_size(width, height)?;
+
+    let mut bit_grid = BitGrid::new(width, height);
+    for (x, y) in cells {
+        bit_grid.set((x - x_min) as usize, (y - y_min) as usize);
+    }
+
+    Ok(bit_grid)
+}
+
+/// Writes the pattern in Life 1.06 format, using the coordinates of the cells in the grid.
+pub fn write(bit_grid: &BitGrid) -> String {
+    let mut output = String::new();
+
+    writeln!(output, "{}", HEADER).unwrap();
+    for y in 0..bit_grid.height() {
+        for x in 0..bit_grid.width() {
+            if bit_grid.get(x, y) {
+                writeln!(output, "{} {}", x, y).unwrap();
+            }
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_negative_coordinates() {
+        let bit_grid = parse("#Life 1.06\n0 -1\n1 0\n-1 1\n0 1\n1 1\n").unwrap();
+
+        assert_eq!(bit_grid.width(), 3);
+        assert_eq!(bit_grid.height(), 3);
+        assert!(bit_grid.get(1, 0));
+        assert!(bit_grid.get(2, 1));
+        assert!(bit_grid.get(0, 2));
+        assert!(bit_grid.get(1, 2));
+        assert!(bit_grid.get(2, 2));
+    }
+
+    #[test]
+    fn parse_empty() {
+        let bit_grid = parse("#Life 1.06\n").unwrap();
+
+        assert_eq!(bit_grid.width(), 0);
+        assert_eq!(bit_grid.height(), 0);
+    }
+
+    #[test]
+    fn parse_error_position() {
+        assert_eq!(
+            parse("#Life 1.06\n0 1\n  2 y\n"),
+            Err("Line 3, column 5: Invalid coordinate 'y'".to_string())
+        );
+        assert_eq!(
+            parse("#Life 1.06\n0 1 2\n"),
+            Err("Line 2, column 1: Expected two coordinates".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_too_large() {
+        let extreme = format!("{} 0\n{} 0\n", i64::MIN, i64::MAX);
+        assert!(parse(&extreme).unwrap_err().starts_with("Pattern spans too many cells"));
+        assert!(parse("0 0\n1000000 1000000\n").unwrap_err().contains("exceeds the maximum"));
+    }
+}
@@ -0,0 +1,261 @@
+// A catalog of well-known Game of Life patterns, which can be placed into a grid in any of the
+// eight orientations that are obtained by rotating and reflecting them.
+// See: https://conwaylife.com/wiki
+use wasm_bindgen::prelude::*;
+use super::{plaintext, BitGrid};
+
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PatternKind {
+    StillLife,
+    Oscillator,
+    Spaceship,
+    Gun,
+}
+
+/// The eight symmetries of the square. Rotations are clockwise.
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Transform {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+    FlipDiagonal,
+    FlipAntiDiagonal,
+}
+
+pub struct Pattern {
+    pub name: &'static str,
+    pub kind: PatternKind,
+    /// The pattern in Plaintext format. All patterns evolve according to B3/S23.
+    pub cells: &'static str,
+}
+
+pub const PATTERNS: [Pattern; 22] = [
+    // Still lifes
+    Pattern { name: "block", kind: PatternKind::StillLife, cells: "OO\nOO" },
+    Pattern { name: "beehive", kind: PatternKind::StillLife, cells: ".OO.\nO..O\n.OO." },
+    Pattern { name: "loaf", kind: PatternKind::StillLife, cells: ".OO.\nO..O\n.O.O\n..O." },
+    Pattern { name: "boat", kind: PatternKind::StillLife, cells: "OO.\nO.O\n.O." },
+    Pattern { name: "ship", kind: PatternKind::StillLife, cells: "OO.\nO.O\n.OO" },
+    Pattern { name: "tub", kind: PatternKind::StillLife, cells: ".O.\nO.O\n.O." },
+    Pattern { name: "pond", kind: PatternKind::StillLife, cells: ".OO.\nO..O\nO..O\n.OO." },
+    Pattern { name: "long boat", kind: PatternKind::StillLife, cells: "OO..\nO.O.\n.O.O\n..O." },
+    Pattern { name: "barge", kind: PatternKind::StillLife, cells: ".O..\nO.O.\n.O.O\n..O." },
+    Pattern { name: "snake", kind: PatternKind::StillLife, cells: "OO.O\nO.OO" },
+    Pattern { name: "eater", kind: PatternKind::StillLife, cells: "OO..\nO.O.\n..O.\n..OO" },
+    Pattern { name: "aircraft carrier", kind: PatternKind::StillLife, cells: "OO..\nO..O\n..OO" },
+    // Oscillators
+    Pattern { name: "blinker", kind: PatternKind::Oscillator, cells: "OOO" },
+    Pattern { name: "toad", kind: PatternKind::Oscillator, cells: ".OOO\nOOO." },
+    Pattern { name: "beacon", kind: PatternKind::Oscillator, cells: "OO..\nOO..\n..OO\n..OO" },
+    Pattern { name: "pulsar", kind: PatternKind::Oscillator, cells: concat!(
+        "..OOO...OOO..\n.............\nO....O.O....O\nO....O.O....O\nO....O.O....O\n",
+        "..OOO...OOO..\n.............\n..OOO...OOO..\nO....O.O....O\nO....O.O....O\n",
+        "O....O.O....O\n.............\n..OOO...OOO..",
+    ) },
+    Pattern {
+        name: "pentadecathlon", kind: PatternKind::Oscillator,
+        cells: "..O....O..\nOO.OOOO.OO\n..O....O..",
+    },
+    // Spaceships
+    Pattern { name: "glider", kind: PatternKind::Spaceship, cells: ".O.\n..O\nOOO" },
+    Pattern { name: "lwss", kind: PatternKind::Spaceship, cells: ".O..O\nO....\nO...O\nOOOO." },
+    Pattern {
+        name: "mwss", kind: PatternKind::Spaceship,
+        cells: "...O..\n.O...O\nO.....\nO....O\nOOOOO.",
+    },
+    Pattern {
+        name: "hwss", kind: PatternKind::Spaceship,
+        cells: "...OO..\n.O....O\nO......\nO.....O\nOOOOOO.",
+    },
+    // Guns
+    Pattern { name: "gosper glider gun", kind: PatternKind::Gun, cells: concat!(
+        "........................O...........\n",
+        "......................O.O...........\n",
+        "............OO......OO............OO\n",
+        "...........O...O....OO............OO\n",
+        "OO........O.....O...OO..............\n",
+        "OO........O...O.OO....O.O...........\n",
+        "..........O.....O.......O...........\n",
+        "...........O...O....................\n",
+        "............OO......................",
+    ) },
+];
+
+pub const TRANSFORMS: [Transform; 8] = [
+    Transform::Identity,
+    Transform::Rotate90,
+    Transform::Rotate180,
+    Transform::Rotate270,
+    Transform::FlipHorizontal,
+    Transform::FlipVertical,
+    Transform::FlipDiagonal,
+    Transform::FlipAntiDiagonal,
+];
+
+/// Returns the pattern with the given name, if it is in the catalog.
+pub fn find(name: &str) -> Option<&'static Pattern> {
+    PATTERNS.iter().find(|pattern| pattern.name == name)
+}
+
+impl Transform {
+    /// Returns true when the transform swaps the width and height of a pattern.
+    pub fn is_transposing(self) -> bool {
+        matches!(
+            self,
+            Transform::Rotate90 | Transform::Rotate270
+                | Transform::FlipDiagonal | Transform::FlipAntiDiagonal
+        )
+    }
+
+    /// Maps a cell of a pattern with the given dimensions to its position in the transformed
+    /// pattern.
+    pub fn apply(self, x: usize, y: usize, width: usize, height: usize) -> (usize, usize) {
+        let (x_max, y_max) = (width - 1, height - 1);
+
+        match self {
+            Transform::Identity => (x, y),
+            Transform::Rotate90 => (y_max - y, x),
+            Transform::Rotate180 => (x_max - x, y_max - y),
+            Transform::Rotate270 => (y, x_max - x),
+            Transform::FlipHorizontal => (x_max - x, y),
+            Transform::FlipVertical => (x, y_max - y),
+            Transform::FlipDiagonal => (y, x),
+            Transform::FlipAntiDiagonal => (y_max - y, x_max - x),
+        }
+    }
+}
+
+impl Pattern {
+    pub fn to_bit_grid(&self, transform: Transform) -> BitGrid {
+        let original = plaintext::parse(self.cells).expect("Invalid pattern in catalog");
+        let (width, height) = (original.width(), original.height());
+
+        let mut bit_grid = if transform.is_transposing() {
+            BitGrid::new(height, width)
+        } else {
+            BitGrid::new(width, height)
+        };
+        for y in 0..height {
+            for x in 0..width {
+                if original.get(x, y) {
+                    let (tx, ty) = transform.apply(x, y, width, height);
+                    bit_grid.set(tx, ty);
+                }
+            }
+        }
+
+        bit_grid
+    }
+}
+
+/// Returns the names of all patterns in the catalog
+#[wasm_bindgen]
+pub fn pattern_names() -> Vec<JsValue> {
+    PATTERNS.iter().map(|pattern| JsValue::from_str(pattern.name)).collect()
+}
+
+#[wasm_bindgen]
+pub fn pattern_kind(name: &str) -> Result<PatternKind, JsValue> {
+    find(name).map(|pattern| pattern.kind).ok_or_else(|| unknown_pattern(name))
+}
+
+#[wasm_bindgen]
+pub fn pattern_bit_grid(name: &str, transform: Transform) -> Result<BitGrid, JsValue> {
+    find(name).map(|pattern| pattern.to_bit_grid(transform)).ok_or_else(|| unknown_pattern(name))
+}
+
+fn unknown_pattern(name: &str) -> JsValue {
+    JsValue::from_str(&format!("Unknown pattern \"{}\"", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{BitCounter, GameOfLife};
+
+    fn place(pattern: &Pattern, transform: Transform, size: usize) -> GameOfLife {
+        let mut gol = GameOfLife::new(size, size, false);
+        gol.place_pattern_result(pattern.name, size / 2 - 8, size / 2 - 8, transform).unwrap();
+        gol
+    }
+
+    #[test]
+    fn transformed_glider() {
+        let glider = find("glider").unwrap();
+        let grids: Vec<String> = TRANSFORMS.iter().map(
+            |&transform| glider.to_bit_grid(transform).to_plaintext()
+        ).collect();
+
+        assert_eq!(grids[0], ".O.\n..O\nOOO\n");
+        assert_eq!(grids[1], "O..\nO.O\nOO.\n");
+        assert_eq!(grids[2], "OOO\nO..\n.O.\n");
+        assert_eq!(grids[3], ".OO\nO.O\n..O\n");
+        assert_eq!(grids[4], ".O.\nO..\nOOO\n");
+        // This phase of the glider has no symmetries
+        for grid in grids.iter() {
+            assert_eq!(grids.iter().filter(|g| *g == grid).count(), 1);
+        }
+    }
+
+    #[test]
+    fn transposed_dimensions() {
+        let lwss = find("lwss").unwrap();
+
+        for &transform in TRANSFORMS.iter() {
+            let bit_grid = lwss.to_bit_grid(transform);
+            let expected = if transform.is_transposing() { (4, 5) } else { (5, 4) };
+            assert_eq!((bit_grid.width(), bit_grid.height()), expected, "{:?}", transform);
+        }
+    }
+
+    #[test]
+    fn catalog_patterns_behave_as_classified() {
+        for pattern in PATTERNS.iter() {
+            for &transform in TRANSFORMS.iter() {
+                let mut gol = place(pattern, transform, 64);
+                let initial = gol.to_bit_grid();
+                let (x0, y0, w0, h0) = initial.bounding_box().unwrap();
+                let shape = initial.crop(x0 as isize, y0 as isize, w0, h0);
+
+                match pattern.kind {
+                    PatternKind::StillLife => {
+                        gol.step();
+                        assert_eq!(gol.to_bit_grid(), initial, "{}", pattern.name);
+                    },
+                    PatternKind::Oscillator => {
+                        gol.step();
+                        assert_ne!(gol.to_bit_grid(), initial, "{}", pattern.name);
+                        let period = (2..=15).find(|_| {
+                            gol.step();
+                            gol.to_bit_grid() == initial
+                        });
+                        assert!(period.is_some(), "{}", pattern.name);
+                    },
+                    PatternKind::Spaceship => {
+                        let moved = (1..=4).any(|_| {
+                            gol.step();
+                            let bit_grid = gol.to_bit_grid();
+                            let (x, y, w, h) = bit_grid.bounding_box().unwrap();
+                            (x, y) != (x0, y0)
+                                && bit_grid.crop(x as isize, y as isize, w, h) == shape
+                        });
+                        assert!(moved, "{}", pattern.name);
+                    },
+                    PatternKind::Gun => {
+                        let bc = BitCounter::new();
+                        let num_cells = bc.count_live_cells(&gol);
+                        for _ in 0..30 {
+                            gol.step();
+                        }
+                        assert_eq!(bc.count_live_cells(&gol), num_cells + 5, "{}", pattern.name);
+                    },
+                }
+            }
+        }
+    }
+}
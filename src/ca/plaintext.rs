@@ -0,0 +1,91 @@
+// Support for the Plaintext pattern format, typically used with the .cells extension.
+// See: https://conwaylife.com/wiki/Plaintext
+use super::{check_pattern_size, parse_error, BitGrid};
+
+/// Parses a pattern in Plaintext format. Lines starting with "!" are comments. Dead cells are
+/// marked with "." and live cells with "O" (or "*"). Trailing dead cells may be omitted, so the
+/// width of the pattern equals the length of its longest line.
+pub fn parse(text: &str) -> Result<BitGrid, String> {
+    let mut rows = Vec::new();
+
+    for (line_no, line) in text.lines().enumerate() {
+        if line.starts_with('!') {
+            continue;
+        }
+
+        let mut row = Vec::new();
+        for (column, ch) in line.trim_end().chars().enumerate() {
+            match ch {
+                '.' => row.push(false),
+                'O' | '*' => row.push(true),
+                _ => return Err(parse_error(
+                    line_no, column, &format!("Unexpected character '{}'", ch)
+                )),
+            }
+        }
+        rows.push(row);
+    }
+
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    check_pattern_size(width, rows.len())?;
+    let mut bit_grid = BitGrid::new(width, rows.len());
+    for (y, row) in rows.iter().enumerate() {
+        for (x, alive) in row.iter().enumerate() {
+            if *alive {
+                bit_grid.set(x, y);
+            }
+        }
+    }
+
+    Ok(bit_grid)
+}
+
+/// Writes the pattern in Plaintext format. Rows are written in full, so that the dimensions of the
+/// pattern are preserved.
+pub fn write(bit_grid: &BitGrid) -> String {
+    let mut output = String::with_capacity((bit_grid.width() + 1) * bit_grid.height());
+
+    for y in 0..bit_grid.height() {
+        for x in 0..bit_grid.width() {
+            output.push(if bit_grid.get(x, y) { 'O' } else { '.' });
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_glider() {
+        let bit_grid = parse("!Name: Glider\n!\n.O\n..O\nOOO\n").unwrap();
+
+        assert_eq!(bit_grid.width(), 3);
+        assert_eq!(bit_grid.height(), 3);
+        assert!(bit_grid.get(1, 0));
+        assert!(bit_grid.get(2, 1));
+        assert!(bit_grid.get(0, 2));
+        assert!(bit_grid.get(1, 2));
+        assert!(bit_grid.get(2, 2));
+        assert!(!bit_grid.get(0, 0));
+        assert!(!bit_grid.get(2, 0));
+    }
+
+    #[test]
+    fn parse_error_position() {
+        assert_eq!(
+            parse("!Comment\n.O.\n.Ox\n"),
+            Err("Line 3, column 3: Unexpected character 'x'".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_too_large() {
+        // A long first row followed by many short ones
+        let text = format!("{}\n{}", ".".repeat(20_000), "O\n".repeat(20_000));
+        assert!(parse(&text).unwrap_err().contains("exceeds the maximum"));
+    }
+}
@@ -0,0 +1,281 @@
+// Support for the Run Length Encoded (RLE) pattern format, as used by Golly and the LifeWiki.
+// See: https://conwaylife.com/wiki/Run_Length_Encoded
+use std::fmt::Write;
+use super::{check_pattern_size, parse_error, BitGrid, Rule};
+
+// The maximum line length of generated RLE output
+const MAX_LINE_LENGTH: usize = 70;
+
+struct Header {
+    width: usize,
+    height: usize,
+    rule: Option<Rule>,
+}
+
+struct RleWriter {
+    output: String,
+    line_length: usize,
+}
+
+fn parse_header(line_no: usize, line: &str) -> Result<Header, String> {
+    let mut width = None;
+    let mut height = None;
+    let mut rule = None;
+
+    let mut column = 0;
+    for item in line.split(',') {
+        let mut parts = item.splitn(2, '=');
+        let key = parts.next().unwrap().trim();
+        let value = match parts.next() {
+            Some(value) => value.trim(),
+            None => return Err(parse_error(line_no, column, "Expected key = value")),
+        };
+
+        match key {
+            "x" | "y" => {
+                let size = value.parse::<usize>().map_err(
+                    |_| parse_error(line_no, column, &format!("Invalid size '{}'", value))
+                )?;
+                if key == "x" { width = Some(size) } else { height = Some(size) }
+            },
+            "rule" => {
+                // Ignore any bounded grid suffix, e.g. "B3/S23:T64,64"
+                let rulestring = value.split(':').next().unwrap();
+                rule = Some(rulestring.parse::<Rule>().map_err(
+                    |msg| parse_error(line_no, column, &msg)
+                )?);
+            },
+            _ => return Err(parse_error(line_no, column, &format!("Unknown key '{}'", key))),
+        }
+
+        column += item.len() + 1;
+    }
+
+    match (width, height) {
+        (Some(width), Some(height)) => {
+            check_pattern_size(width, height).map_err(|msg| parse_error(line_no, 0, &msg))?;
+            Ok(Header { width, height, rule })
+        },
+        _ => Err(parse_error(line_no, 0, "Header should specify x and y")),
+    }
+}
+
+/// Parses a pattern in RLE format. Returns the pattern, sized as specified by the header, together
+/// with the rule, when the header specifies one.
+pub fn parse(rle: &str) -> Result<(BitGrid, Option<Rule>), String> {
+    let mut header = None;
+    let mut bit_grid = BitGrid::new(0, 0);
+    let mut x: usize = 0;
+    let mut y: usize = 0;
+    let mut count: Option<usize> = None;
+
+    for (line_no, line) in rle.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if header.is_none() {
+            let h = parse_header(line_no, trimmed)?;
+            bit_grid = BitGrid::new(h.width, h.height);
+            header = Some(h);
+            continue;
+        }
+
+        for (column, ch) in line.chars().enumerate() {
+            if let Some(digit) = ch.to_digit(10) {
+                let value = count.unwrap_or(0).checked_mul(10)
+                    .and_then(|value| value.checked_add(digit as usize));
+                count = Some(value.ok_or_else(|| parse_error(line_no, column, "Count too large"))?);
+                continue;
+            }
+            if ch.is_whitespace() {
+                if count.is_some() {
+                    return Err(parse_error(line_no, column, "Expected tag after count"));
+                }
+                continue;
+            }
+
+            let run = count.take().unwrap_or(1);
+            match ch {
+                'b' | '.' => x = x.saturating_add(run),
+                '$' => {
+                    x = 0;
+                    y = y.saturating_add(run);
+                },
+                '!' => return Ok((bit_grid, header.unwrap().rule)),
+                _ if ch.is_ascii_alphabetic() => {
+                    // Tag "o" marks live cells. Other letters are used for multi-state rules. As
+                    // there are only two states, these are all treated as alive.
+                    if x.saturating_add(run) > bit_grid.width() || y >= bit_grid.height() {
+                        return Err(parse_error(line_no, column, "Pattern exceeds specified size"));
+                    }
+                    for _ in 0..run {
+                        bit_grid.set(x, y);
+                        x += 1;
+                    }
+                },
+                _ => return Err(parse_error(
+                    line_no, column, &format!("Unexpected character '{}'", ch)
+                )),
+            }
+        }
+    }
+
+    match header {
+        // Be lenient when the terminating "!" is missing
+        Some(h) => Ok((bit_grid, h.rule)),
+        None => Err("Missing header".to_string()),
+    }
+}
+
+impl RleWriter {
+    fn new() -> Self {
+        RleWriter {
+            output: String::new(),
+            line_length: 0,
+        }
+    }
+
+    fn write_run(&mut self, count: usize, tag: char) {
+        if count == 0 {
+            return;
+        }
+
+        let item = if count == 1 { format!("{}", tag) } else { format!("{}{}", count, tag) };
+        if self.line_length + item.len() > MAX_LINE_LENGTH {
+            self.output.push('\n');
+            self.line_length = 0;
+        }
+        self.output.push_str(&item);
+        self.line_length += item.len();
+    }
+}
+
+/// Writes the pattern in RLE format. The header includes the rule, when it is specified.
+pub fn write(bit_grid: &BitGrid, rule: Option<&Rule>) -> String {
+    let mut writer = RleWriter::new();
+
+    write!(writer.output, "x = {}, y = {}", bit_grid.width(), bit_grid.height()).unwrap();
+    if let Some(rule) = rule {
+        write!(writer.output, ", rule = {}", rule).unwrap();
+    }
+    writer.output.push('\n');
+
+    // Empty rows are only written once they are followed by a non-empty one
+    let mut pending_rows = 0;
+    for y in 0..bit_grid.height() {
+        let mut x = 0;
+        let mut dead_run = 0;
+
+        while x < bit_grid.width() {
+            let alive = bit_grid.get(x, y);
+            let mut run = 1;
+            while x + run < bit_grid.width() && bit_grid.get(x + run, y) == alive {
+                run += 1;
+            }
+
+            if alive {
+                writer.write_run(pending_rows, '$');
+                pending_rows = 0;
+                writer.write_run(dead_run, 'b');
+                dead_run = 0;
+                writer.write_run(run, 'o');
+            } else {
+                // Trailing dead cells are omitted
+                dead_run = run;
+            }
+
+            x += run;
+        }
+
+        pending_rows += 1;
+    }
+    writer.write_run(1, '!');
+    writer.output.push('\n');
+
+    writer.output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GLIDER: &str = "#N Glider\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!\n";
+
+    #[test]
+    fn parse_glider() {
+        let (bit_grid, rule) = parse(GLIDER).unwrap();
+
+        assert_eq!(bit_grid.width(), 3);
+        assert_eq!(bit_grid.height(), 3);
+        assert_eq!(rule, Some(Rule::conway()));
+
+        assert!(bit_grid.get(1, 0));
+        assert!(bit_grid.get(2, 1));
+        assert!(bit_grid.get(0, 2));
+        assert!(bit_grid.get(1, 2));
+        assert!(bit_grid.get(2, 2));
+        assert!(!bit_grid.get(0, 0));
+        assert!(!bit_grid.get(1, 1));
+    }
+
+    #[test]
+    fn parse_without_rule_and_multiline() {
+        let (bit_grid, rule) = parse("x = 5, y = 4\n2o$\n\n2$4bo\n!").unwrap();
+
+        assert_eq!(rule, None);
+        assert!(bit_grid.get(0, 0));
+        assert!(bit_grid.get(1, 0));
+        assert!(bit_grid.get(4, 3));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(parse("bo$2bo$3o!").is_err());
+        assert!(parse("x = 3\nbo$2bo$3o!").is_err());
+        assert!(parse("x = 2, y = 3\nbo$2bo$3o!").is_err());
+        assert_eq!(
+            parse("x = 3, y = 3\nbo$2b?$3o!"),
+            Err("Line 2, column 6: Unexpected character '?'".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_too_large() {
+        let huge = format!("x = {}, y = {}\n!", usize::MAX, usize::MAX);
+        assert!(parse(&huge).unwrap_err().contains("exceeds the maximum"));
+        assert!(parse("x = 100000, y = 100000\n!").unwrap_err().contains("exceeds the maximum"));
+        // Runs that overflow are reported rather than wrapping around
+        let long_run = format!("x = 3, y = 3\n{}0o!", usize::MAX);
+        assert_eq!(parse(&long_run), Err("Line 2, column 21: Count too large".to_string()));
+        let skips = format!("x = 3, y = 3\n{}b{}bo!", usize::MAX, usize::MAX);
+        assert!(parse(&skips).unwrap_err().contains("Pattern exceeds specified size"));
+    }
+
+    #[test]
+    fn write_glider() {
+        let (bit_grid, rule) = parse(GLIDER).unwrap();
+
+        assert_eq!(
+            write(&bit_grid, rule.as_ref()),
+            "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n"
+        );
+    }
+
+    #[test]
+    fn round_trip_with_empty_rows_and_long_lines() {
+        let mut bit_grid = BitGrid::new(100, 7);
+        for x in (0..100).step_by(2) {
+            bit_grid.set(x, 1);
+        }
+        bit_grid.set(50, 5);
+
+        let rle = write(&bit_grid, None);
+        assert!(rle.lines().all(|line| line.len() <= MAX_LINE_LENGTH));
+
+        let (parsed, rule) = parse(&rle).unwrap();
+        assert_eq!(rule, None);
+        assert_eq!(parsed, bit_grid);
+    }
+}
@@ -0,0 +1,215 @@
+use std::fmt;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use super::UnitType;
+
+/// An outer-totalistic ("Life-like") rule. Whether a cell is alive in the next generation only
+/// depends on its current state and on the number of live cells in its Moore neighbourhood.
+///
+/// Rules are typically written in B/S notation, e.g. `B3/S23` for Conway's Game of Life, where
+/// the digits after the `B` are the neighbour counts for which a dead cell becomes alive and the
+/// digits after the `S` are the neighbour counts for which a live cell survives.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Rule {
+    // Bit N is set iff a dead cell with N live neighbours becomes alive
+    birth: u16,
+    // Bit N is set iff a live cell with N live neighbours stays alive
+    survival: u16,
+}
+
+const MAX_NEIGHBOURS: usize = 8;
+
+impl Rule {
+    pub fn new(birth: &[usize], survival: &[usize]) -> Result<Self, String> {
+        Ok(Rule {
+            birth: Rule::to_mask(birth)?,
+            survival: Rule::to_mask(survival)?,
+        })
+    }
+
+    /// Conway's Game of Life: B3/S23
+    pub fn conway() -> Self {
+        Rule {
+            birth: 0x1 << 3,
+            survival: 0x1 << 2 | 0x1 << 3,
+        }
+    }
+
+    fn to_mask(counts: &[usize]) -> Result<u16, String> {
+        let mut mask = 0;
+
+        for &count in counts {
+            if count > MAX_NEIGHBOURS {
+                return Err(format!("Invalid neighbour count {}", count));
+            }
+            mask |= 0x1 << count;
+        }
+
+        Ok(mask)
+    }
+
+    fn parse_counts(digits: &str) -> Result<u16, String> {
+        let mut mask = 0;
+
+        for ch in digits.chars() {
+            match ch.to_digit(10) {
+                Some(count) if count as usize <= MAX_NEIGHBOURS => mask |= 0x1 << count,
+                _ => return Err(format!("Invalid neighbour count '{}'", ch)),
+            }
+        }
+
+        Ok(mask)
+    }
+
+    pub fn is_conway(&self) -> bool {
+        *self == Rule::conway()
+    }
+
+    pub fn births_on(&self, num_neighbours: usize) -> bool {
+        (self.birth >> num_neighbours) & 0x1 == 0x1
+    }
+
+    pub fn survives_on(&self, num_neighbours: usize) -> bool {
+        (self.survival >> num_neighbours) & 0x1 == 0x1
+    }
+
+    // Evaluates the rule for all bits in parallel. Each of the neighbour count arguments holds one
+    // bit of the (four-bit) number of live neighbours of each cell, least significant bit first.
+    pub(crate) fn apply(&self, current: UnitType, count: [UnitType; 4]) -> UnitType {
+        let mut birth = 0;
+        let mut survival = 0;
+
+        for num_neighbours in 0..=MAX_NEIGHBOURS {
+            let births = self.births_on(num_neighbours);
+            let survives = self.survives_on(num_neighbours);
+
+            if births || survives {
+                let mut matches = !0;
+                for (i, &bit) in count.iter().enumerate() {
+                    matches &= if (num_neighbours >> i) & 0x1 == 0x1 { bit } else { !bit };
+                }
+
+                if births {
+                    birth |= matches;
+                }
+                if survives {
+                    survival |= matches;
+                }
+            }
+        }
+
+        (current & survival) | (!current & birth)
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Rule::conway()
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    /// Parses a rule in B/S notation, e.g. "B36/S23". The order of both parts does not matter and
+    /// the case of the prefixes is ignored. The traditional S/B notation without prefixes (e.g.
+    /// "23/36") is also accepted.
+    fn from_str(rulestring: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = rulestring.trim().split('/').collect();
+        if parts.len() != 2 {
+            return Err(format!("Invalid rule '{}'", rulestring));
+        }
+
+        let mut birth = None;
+        let mut survival = None;
+        for part in parts.iter() {
+            let mut chars = part.chars();
+            match chars.next().map(|ch| ch.to_ascii_uppercase()) {
+                Some('B') if birth.is_none() => birth = Some(Rule::parse_counts(chars.as_str())?),
+                Some('S') if survival.is_none() => {
+                    survival = Some(Rule::parse_counts(chars.as_str())?)
+                },
+                _ => break
+            }
+        }
+
+        if let (Some(birth), Some(survival)) = (birth, survival) {
+            return Ok(Rule { birth, survival });
+        }
+
+        if parts.iter().all(|part| part.chars().all(|ch| ch.is_ascii_digit())) {
+            // S/B notation
+            return Ok(Rule {
+                birth: Rule::parse_counts(parts[1])?,
+                survival: Rule::parse_counts(parts[0])?,
+            });
+        }
+
+        Err(format!("Invalid rule '{}'", rulestring))
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "B")?;
+        for n in (0..=MAX_NEIGHBOURS).filter(|n| self.births_on(*n)) {
+            write!(f, "{}", n)?;
+        }
+        write!(f, "/S")?;
+        for n in (0..=MAX_NEIGHBOURS).filter(|n| self.survives_on(*n)) {
+            write!(f, "{}", n)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_conway() {
+        let rule: Rule = "B3/S23".parse().unwrap();
+
+        assert!(rule.is_conway());
+        assert_eq!(format!("{}", rule), "B3/S23");
+    }
+
+    #[test]
+    fn parse_variants() {
+        assert_eq!("b36/s23".parse::<Rule>(), Rule::new(&[3, 6], &[2, 3]));
+        assert_eq!("S23/B36".parse::<Rule>(), Rule::new(&[3, 6], &[2, 3]));
+        assert_eq!("23/36".parse::<Rule>(), Rule::new(&[3, 6], &[2, 3]));
+        assert_eq!("B2/S".parse::<Rule>(), Rule::new(&[2], &[]));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!("B3S23".parse::<Rule>().is_err());
+        assert!("B39/S23".parse::<Rule>().is_err());
+        assert!("B3/B23".parse::<Rule>().is_err());
+        assert!("X3/S23".parse::<Rule>().is_err());
+    }
+
+    #[test]
+    fn apply_matches_counts() {
+        let rule: Rule = "B3678/S34678".parse().unwrap();
+
+        for &current in [0, 1].iter() {
+            for count in 0..=MAX_NEIGHBOURS {
+                let mut bits = [0; 4];
+                for (i, bit) in bits.iter_mut().enumerate() {
+                    *bit = ((count >> i) & 1) as UnitType;
+                }
+                let expected = if current == 1 {
+                    rule.survives_on(count)
+                } else {
+                    rule.births_on(count)
+                };
+
+                assert_eq!(rule.apply(current, bits) & 1 == 1, expected);
+            }
+        }
+    }
+}
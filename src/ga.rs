@@ -4,7 +4,12 @@ use std::rc::Rc;
 use std::ops::Deref;
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
-use rand::{self, Rng};
+use std::cmp::Ordering;
+use rand::{Rng, RngCore, SeedableRng};
+use rand_pcg::Pcg64Mcg;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use nsga2::Nsga2Selection;
+use steady_state::{Replacement, SteadyState};
 
 /// A genotype encodes a solution to the optimisation problem.
 pub trait Genotype : 'static + fmt::Debug + clone::Clone {
@@ -33,32 +38,67 @@ pub trait Evaluator<P: Phenotype> : fmt::Debug {
 
     fn evaluate(&mut self, phenotype: &P) -> f32;
 
+    /// Evaluates each of the phenotypes independently. Evaluators that can evaluate many phenotypes
+    /// at once more efficiently than one at a time should override it.
+    fn evaluate_batch(&mut self, phenotypes: &[&P]) -> Vec<f32> {
+        phenotypes.iter().map(|phenotype| self.evaluate(phenotype)).collect()
+    }
+
+    /// Returns true when the fitness of a phenotype depends on the other phenotypes it is
+    /// evaluated with, for example because seeds compete in a shared garden, or because fitness
+    /// is relative to the rest of the population. The population is then evaluated using
+    /// [Self::bulk_evaluate] instead, and fitness values are never cached.
+    fn is_interaction_based(&self) -> bool {
+        false
+    }
+
+    /// Evaluates the phenotypes of all individuals in the population together. Returns the
+    /// fitness of each, in the same order. A phenotype that is shared by multiple individuals is
+    /// included once for each of them. Only invoked for interaction-based evaluators.
+    fn bulk_evaluate(&mut self, phenotypes: &[&P]) -> Vec<f32> {
+        self.evaluate_batch(phenotypes)
+    }
+
+    /// The number of objectives of multi-objective evaluators. Single-objective evaluators, which
+    /// only determine a fitness value, return one.
+    fn num_objectives(&self) -> usize {
+        1
+    }
+
+    /// Evaluates each of the phenotypes independently, returning the value of each objective.
+    /// Objectives are maximised. Multi-objective evaluators should override it.
+    fn evaluate_objectives(&mut self, phenotypes: &[&P]) -> Vec<Vec<f32>> {
+        self.evaluate_batch(phenotypes).into_iter().map(|fitness| vec![fitness]).collect()
+    }
+
     fn as_any(&self) -> &dyn Any;
 
-    // TODO: Extend with bulk_evaluate to support interaction-based fitness
+    fn as_any_mut(&mut self) -> &mut dyn Any;
 }
 
+// All random choices are made using the random number generator that is passed in, so that runs
+// can be reproduced by seeding it.
 pub trait Mutation {
     type Genotype;
 
-    fn mutate(&self, target: &mut Self::Genotype);
+    fn mutate(&self, target: &mut Self::Genotype, rng: &mut dyn RngCore);
 }
 
 pub trait Recombination {
     type Genotype;
 
     fn recombine(
-        &self, parent1: &Self::Genotype, parent1: &Self::Genotype
+        &self, parent1: &Self::Genotype, parent1: &Self::Genotype, rng: &mut dyn RngCore
     ) -> Self::Genotype;
 }
 
 pub trait GenotypeFactory<G: Genotype> {
-    fn create(&self) -> G;
+    fn create(&self, rng: &mut dyn RngCore) -> G;
 }
 
 pub trait GenotypeManipulation<G: Genotype> {
-    fn mutate(&self, target: &mut G);
-    fn recombine(&self, parent1: &G, parent2: &G) -> G;
+    fn mutate(&self, target: &mut G, rng: &mut dyn RngCore);
+    fn recombine(&self, parent1: &G, parent2: &G, rng: &mut dyn RngCore) -> G;
 }
 
 pub trait GenotypeConfig<G: Genotype>:
@@ -67,14 +107,23 @@ pub trait GenotypeConfig<G: Genotype>:
 #[derive(Debug)]
 pub struct MyRef<T>(Rc<T>);
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Individual<G: Genotype, P: Phenotype> {
     genotype: MyRef<G>,
     phenotype: Option<MyRef<P>>,
     fitness: Option<f32>,
+    // Only set by multi-objective evaluators. The first objective then also serves as fitness
+    objectives: Option<Vec<f32>>,
+}
+
+// The outcome of evaluating a phenotype
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Evaluation {
+    fitness: f32,
+    objectives: Option<Vec<f32>>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 enum PopulationState {
     Empty,
     GenotypeCreated,
@@ -82,10 +131,13 @@ enum PopulationState {
     FitnessEvaluated,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct Population<G: Genotype, P: Phenotype> {
     individuals: Vec<Individual<G, P>>,
-    fitness_cache: Option<HashMap<MyRef<P>, f32>>,
+    fitness_cache: Option<HashMap<MyRef<P>, Evaluation>>,
     generation: u32,
+    // The number of offspring added during the current generation by steady-state evolution
+    num_children: usize,
     state: PopulationState,
 }
 
@@ -102,7 +154,9 @@ pub trait Selection<G: Genotype, P: Phenotype> : fmt::Debug {
     }
 
     // Selects an individual.
-    fn select_from<'a>(&mut self, population: &'a Population<G, P>) -> &'a Individual<G, P>;
+    fn select_from<'a>(
+        &mut self, population: &'a Population<G, P>, rng: &mut dyn RngCore
+    ) -> &'a Individual<G, P>;
 
 }
 
@@ -119,6 +173,16 @@ pub struct CountingEvaluator<P: Phenotype> {
     num_evaluations: u32,
 }
 
+/// The state of an evolutionary algorithm that changes while it runs. Together with the
+/// configuration of the algorithm, it determines how the run continues.
+#[derive(Serialize, Deserialize)]
+pub struct EaState<G: Genotype, P: Phenotype> {
+    population: Population<G, P>,
+    num_evaluations: u32,
+    seed: u64,
+    rng: Pcg64Mcg,
+}
+
 #[derive(Debug)]
 pub struct OptimizationStats {
     pub num_generations: u32,
@@ -135,12 +199,20 @@ pub struct EvolutionaryAlgorithm<G: Genotype, P: Phenotype> {
     selection: Box<dyn Selection<G, P>>,
     config: Box<dyn GenotypeConfig<G>>,
     population: Population<G, P>,
+    // When set, offspring compete with their parents for survival as done by NSGA-II
+    nsga2: bool,
+    // When set, each step only creates a few offspring, which replace existing individuals
+    steady_state: Option<SteadyState>,
+    // The seed that the random number generator was initialised with
+    seed: u64,
+    rng: Pcg64Mcg,
 }
 
 impl<T> Deref for MyRef<T> {
 
     type Target = T;
 
+    #[allow(clippy::explicit_auto_deref)]
     fn deref(&self) -> &Self::Target {
         &*self.0
     }
@@ -171,18 +243,44 @@ impl<T> MyRef<T> {
         MyRef(Rc::new(wrapped))
     }
 
-    pub fn clone(&self) -> Self {
+    // Returns true iff both refer to the same value, not merely to equal values
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Rc::ptr_eq(&this.0, &other.0)
+    }
+
+}
+
+impl<T> clone::Clone for MyRef<T> {
+
+    fn clone(&self) -> Self {
         MyRef(Rc::clone(&self.0))
     }
 
 }
 
+impl<T: Serialize> Serialize for MyRef<T> {
+
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (*self.0).serialize(serializer)
+    }
+
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for MyRef<T> {
+
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(MyRef::new)
+    }
+
+}
+
 impl<G: Genotype, P: Phenotype> Individual<G, P> {
     pub fn new(genotype: G) -> Self {
         Individual {
             genotype: MyRef::new(genotype),
             phenotype: None,
-            fitness: None
+            fitness: None,
+            objectives: None,
         }
     }
 
@@ -197,9 +295,23 @@ impl<G: Genotype, P: Phenotype> Individual<G, P> {
             None
         }
     }
+
+    pub fn fitness(&self) -> Option<f32> {
+        self.fitness
+    }
+
+    pub fn objectives(&self) -> Option<&[f32]> {
+        self.objectives.as_deref()
+    }
+
+    fn set_evaluation(&mut self, evaluation: &Evaluation) {
+        self.fitness = Some(evaluation.fitness);
+        self.objectives = evaluation.objectives.clone();
+    }
 }
 
 impl<G: Genotype, P: Phenotype> clone::Clone for Individual<G, P> {
+    #[allow(clippy::manual_map)]
     fn clone(&self) -> Self {
         Individual {
             genotype: self.genotype.clone(),
@@ -208,16 +320,26 @@ impl<G: Genotype, P: Phenotype> clone::Clone for Individual<G, P> {
                 Some(phenotype) => Some(phenotype.clone())
             },
             fitness: self.fitness,
+            objectives: self.objectives.clone(),
         }
     }
 }
 
+// Orders fitness values from low to high, with missing values lowest
+fn fitness_cmp(a: Option<f32>, b: Option<f32>) -> Ordering {
+    match (a, b) {
+        (Some(fa), Some(fb)) => fa.partial_cmp(&fb).unwrap_or(Ordering::Equal),
+        (fa, fb) => fa.is_some().cmp(&fb.is_some()),
+    }
+}
+
 impl<G: Genotype, P: Phenotype> Population<G, P> {
     pub fn with_capacity(capacity: usize) -> Self {
         Population {
             individuals: Vec::with_capacity(capacity),
             fitness_cache: None,
             generation: 1,
+            num_children: 0,
             state: PopulationState::Empty,
         }
     }
@@ -243,6 +365,7 @@ impl<G: Genotype, P: Phenotype> Population<G, P> {
         self.individuals.iter()
     }
 
+    #[allow(unused_parens, clippy::redundant_pattern_matching, clippy::explicit_auto_deref)]
     pub fn grow(&mut self, expressor: &mut(dyn Expressor<G, P>)) {
         assert_eq!(self.state, PopulationState::GenotypeCreated);
 
@@ -257,28 +380,91 @@ impl<G: Genotype, P: Phenotype> Population<G, P> {
         self.state = PopulationState::PhenotypeCreated;
     }
 
-    pub fn evaluate(&mut self, evaluator: &mut(dyn Evaluator<P>)) {
+    /// Determines the fitness of all individuals that do not have one yet. All phenotypes that
+    /// need evaluating are passed to the evaluator in one batch. When the fitness cache is
+    /// enabled, each distinct phenotype is evaluated at most once.
+    ///
+    /// Interaction-based evaluators instead (re-)evaluate all individuals together, as their
+    /// fitness depends on the rest of the population. The fitness cache is bypassed for these.
+    pub fn evaluate(&mut self, evaluator: &mut dyn Evaluator<P>) {
         assert_eq!(self.state, PopulationState::PhenotypeCreated);
 
-        for indiv in self.individuals.iter_mut() {
+        if evaluator.is_interaction_based() {
+            self.bulk_evaluate(evaluator);
+            self.state = PopulationState::FitnessEvaluated;
+            return;
+        }
+
+        // The phenotypes to evaluate, and for each individual without fitness the index of its
+        // phenotype in this list
+        let mut pending: Vec<MyRef<P>> = Vec::new();
+        let mut pending_index: HashMap<MyRef<P>, usize> = HashMap::new();
+        let mut assignments: Vec<(usize, usize)> = Vec::new();
+
+        for (i, indiv) in self.individuals.iter_mut().enumerate() {
             if let Some(phenotype) = &indiv.phenotype {
-                if let None = indiv.fitness {
-                    (*indiv).fitness = Some(
-                        if let Some(cache) = &mut self.fitness_cache {
-                            *cache.entry(phenotype.clone()).or_insert_with_key(
-                                |phenotype| evaluator.evaluate(&phenotype)
-                            )
-                        } else {
-                            evaluator.evaluate(&phenotype)
-                        }
-                    )
+                if indiv.fitness.is_some() {
+                    continue;
+                }
+
+                if let Some(cache) = &self.fitness_cache {
+                    if let Some(evaluation) = cache.get(phenotype) {
+                        indiv.set_evaluation(evaluation);
+                        continue;
+                    }
+
+                    let index = *pending_index.entry(phenotype.clone()).or_insert_with(|| {
+                        pending.push(phenotype.clone());
+                        pending.len() - 1
+                    });
+                    assignments.push((i, index));
+                } else {
+                    pending.push(phenotype.clone());
+                    assignments.push((i, pending.len() - 1));
                 }
             }
         }
 
+        if !pending.is_empty() {
+            let phenotypes: Vec<&P> = pending.iter().map(|phenotype| &**phenotype).collect();
+            let evaluations: Vec<Evaluation> = if evaluator.num_objectives() > 1 {
+                evaluator.evaluate_objectives(&phenotypes).into_iter().map(
+                    |objectives| Evaluation { fitness: objectives[0], objectives: Some(objectives) }
+                ).collect()
+            } else {
+                evaluator.evaluate_batch(&phenotypes).into_iter().map(
+                    |fitness| Evaluation { fitness, objectives: None }
+                ).collect()
+            };
+            assert_eq!(evaluations.len(), pending.len());
+
+            for (i, index) in assignments {
+                self.individuals[i].set_evaluation(&evaluations[index]);
+            }
+            if let Some(cache) = &mut self.fitness_cache {
+                cache.extend(pending.into_iter().zip(evaluations));
+            }
+        }
+
         self.state = PopulationState::FitnessEvaluated;
     }
 
+    fn bulk_evaluate(&mut self, evaluator: &mut dyn Evaluator<P>) {
+        let phenotypes: Vec<&P> = self.individuals.iter().map(
+            |indiv| indiv.phenotype().expect("Individual without phenotype")
+        ).collect();
+        if phenotypes.is_empty() {
+            return;
+        }
+
+        let fitness = evaluator.bulk_evaluate(&phenotypes);
+        assert_eq!(fitness.len(), phenotypes.len());
+
+        for (indiv, fitness) in self.individuals.iter_mut().zip(fitness) {
+            indiv.fitness = Some(fitness);
+        }
+    }
+
     pub fn new_generation(&mut self, new_indivs: Vec<Individual<G, P>>) {
         assert_eq!(self.state, PopulationState::FitnessEvaluated);
         assert_eq!(new_indivs.len(), self.size());
@@ -289,6 +475,55 @@ impl<G: Genotype, P: Phenotype> Population<G, P> {
         self.state = PopulationState::GenotypeCreated;
     }
 
+    /// Starts a new generation that consists of the current individuals together with their
+    /// offspring. It should subsequently be reduced to its original size, once the offspring has
+    /// been evaluated.
+    pub fn add_offspring(&mut self, offspring: Vec<Individual<G, P>>) {
+        assert_eq!(self.state, PopulationState::FitnessEvaluated);
+
+        self.individuals.extend(offspring);
+        self.generation += 1;
+
+        self.state = PopulationState::GenotypeCreated;
+    }
+
+    /// Adds offspring to the current generation, as done by steady-state evolution. They should
+    /// subsequently replace existing individuals, once evaluated. A new generation starts each
+    /// time that as many offspring have been added as there are individuals.
+    pub fn add_children(&mut self, offspring: Vec<Individual<G, P>>) {
+        assert_eq!(self.state, PopulationState::FitnessEvaluated);
+
+        self.num_children += offspring.len();
+        while self.num_children >= self.size() {
+            self.num_children -= self.size();
+            self.generation += 1;
+        }
+        self.individuals.extend(offspring);
+
+        self.state = PopulationState::GenotypeCreated;
+    }
+
+    /// Replaces the least fit individuals by the given ones. Individuals without fitness are
+    /// replaced first. When there are more newcomers than individuals, only the fittest
+    /// newcomers are kept, and they replace all individuals.
+    pub fn replace_worst(&mut self, mut newcomers: Vec<Individual<G, P>>) {
+        assert_eq!(self.state, PopulationState::FitnessEvaluated);
+
+        if newcomers.len() > self.size() {
+            newcomers.sort_by(|a, b| fitness_cmp(b.fitness, a.fitness));
+            newcomers.truncate(self.size());
+        }
+
+        let mut ranking: Vec<usize> = (0..self.size()).collect();
+        ranking.sort_by(
+            |&a, &b| fitness_cmp(self.individuals[a].fitness, self.individuals[b].fitness)
+        );
+
+        for (index, newcomer) in ranking.into_iter().zip(newcomers) {
+            self.individuals[index] = newcomer;
+        }
+    }
+
     pub fn get_stats(&self) -> Option<PopulationStats<G, P>> {
         let mut max: Option<f32> = None;
         let mut sum: f32 = 0f32;
@@ -322,6 +557,18 @@ impl<G: Genotype, P: Phenotype> Population<G, P> {
     }
 }
 
+impl<G: Genotype, P: Phenotype> clone::Clone for Population<G, P> {
+    fn clone(&self) -> Self {
+        Population {
+            individuals: self.individuals.clone(),
+            fitness_cache: self.fitness_cache.clone(),
+            generation: self.generation,
+            num_children: self.num_children,
+            state: self.state.clone(),
+        }
+    }
+}
+
 impl<G: Genotype, P: Phenotype> fmt::Debug for Population<G, P> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for individual in self.individuals.iter() {
@@ -353,9 +600,36 @@ impl<P: Phenotype> Evaluator<P> for CountingEvaluator<P> {
         self.evaluator.evaluate(phenotype)
     }
 
+    fn evaluate_batch(&mut self, phenotypes: &[&P]) -> Vec<f32> {
+        self.num_evaluations += phenotypes.len() as u32;
+        self.evaluator.evaluate_batch(phenotypes)
+    }
+
+    fn num_objectives(&self) -> usize {
+        self.evaluator.num_objectives()
+    }
+
+    fn evaluate_objectives(&mut self, phenotypes: &[&P]) -> Vec<Vec<f32>> {
+        self.num_evaluations += phenotypes.len() as u32;
+        self.evaluator.evaluate_objectives(phenotypes)
+    }
+
+    fn is_interaction_based(&self) -> bool {
+        self.evaluator.is_interaction_based()
+    }
+
+    fn bulk_evaluate(&mut self, phenotypes: &[&P]) -> Vec<f32> {
+        self.num_evaluations += phenotypes.len() as u32;
+        self.evaluator.bulk_evaluate(phenotypes)
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
 }
 
 impl<G: Genotype, P: Phenotype> EvolutionaryAlgorithm<G, P> {
@@ -366,6 +640,8 @@ impl<G: Genotype, P: Phenotype> EvolutionaryAlgorithm<G, P> {
         evaluator: Box<dyn Evaluator<P>>,
         selection: Box<dyn Selection<G, P>>
     ) -> Self {
+        let seed = rand::thread_rng().gen();
+
         EvolutionaryAlgorithm {
             pop_size,
             config,
@@ -375,9 +651,25 @@ impl<G: Genotype, P: Phenotype> EvolutionaryAlgorithm<G, P> {
             evaluator: CountingEvaluator::new(evaluator),
             selection,
             population: Population::with_capacity(pop_size),
+            nsga2: false,
+            steady_state: None,
+            seed,
+            rng: Pcg64Mcg::seed_from_u64(seed),
         }
     }
 
+    /// Seeds the random number generator. Runs with the same seed and settings are identical.
+    /// When not set, a random seed is used, which is available via [Self::seed].
+    pub fn set_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self.rng = Pcg64Mcg::seed_from_u64(seed);
+        self
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
     pub fn set_recombination_prob(mut self, prob: f32) -> Self {
         self.recombination_prob = prob;
         self
@@ -388,14 +680,44 @@ impl<G: Genotype, P: Phenotype> EvolutionaryAlgorithm<G, P> {
         self
     }
 
+    /// Uses NSGA-II for multi-objective optimisation. This replaces the selection scheme. Each
+    /// generation, parents and offspring are ranked together, and only the best survive.
+    pub fn enable_nsga2(mut self) -> Self {
+        assert!(self.steady_state.is_none(), "NSGA-II does not support steady-state evolution");
+        self.selection = Box::new(Nsga2Selection::new());
+        self.nsga2 = true;
+        self
+    }
+
+    /// Uses steady-state evolution. Each step then creates the given number of offspring, which
+    /// replace existing individuals according to the replacement policy. The tournament size is
+    /// only used for inverse tournaments. Elitism should not be used for parent selection, as
+    /// the elite would be selected as parent every step. Interaction-based evaluators are not
+    /// supported, as these would re-evaluate the whole population every step.
+    pub fn enable_steady_state(
+        mut self, num_offspring: usize, replacement: Replacement, tournament_size: usize
+    ) -> Self {
+        assert!(!self.nsga2, "NSGA-II does not support steady-state evolution");
+        assert!(
+            !self.evaluator.is_interaction_based(),
+            "Steady-state evolution does not support interaction-based evaluators"
+        );
+        self.steady_state = Some(SteadyState::new(num_offspring, replacement, tournament_size));
+        self
+    }
+
     pub fn enable_fitness_cache(mut self) -> Self {
         self.population.fitness_cache = Some(HashMap::new());
         self
     }
 
-    pub fn evaluator(&self) -> &Box<dyn Evaluator<P>> {
+    pub fn evaluator(&self) -> &dyn Evaluator<P> {
         // Return wrapped evaluator to hide wrapping
-        &self.evaluator.evaluator
+        &*self.evaluator.evaluator
+    }
+
+    pub fn evaluator_mut(&mut self) -> &mut dyn Evaluator<P> {
+        &mut *self.evaluator.evaluator
     }
 
     pub fn population(&self) -> &Population<G, P> {
@@ -413,7 +735,7 @@ impl<G: Genotype, P: Phenotype> EvolutionaryAlgorithm<G, P> {
     pub fn populate(&mut self) {
         while self.population.size() < self.pop_size {
             self.population.add_individual(
-                Individual::new(self.config.create())
+                Individual::new(self.config.create(&mut self.rng))
             );
         }
     }
@@ -426,30 +748,35 @@ impl<G: Genotype, P: Phenotype> EvolutionaryAlgorithm<G, P> {
         self.population.evaluate(&mut self.evaluator);
     }
 
-    fn new_genotype(&mut self) -> G {
-        let mut genotype = if rand::thread_rng().gen::<f32>() < self.recombination_prob {
-            let parent1 = (*self.selection).select_from(&self.population);
-            let parent2 = (*self.selection).select_from(&self.population);
-            self.config.recombine(&parent1.genotype, &parent2.genotype)
+    // Returns a new genotype together with the genotypes of its parents
+    fn new_genotype(&mut self) -> (G, Vec<MyRef<G>>) {
+        let rng = &mut self.rng;
+        let (mut genotype, parents) = if rng.gen::<f32>() < self.recombination_prob {
+            let parent1 = (*self.selection).select_from(&self.population, rng);
+            let parent2 = (*self.selection).select_from(&self.population, rng);
+            (
+                self.config.recombine(&parent1.genotype, &parent2.genotype, rng),
+                vec![parent1.genotype.clone(), parent2.genotype.clone()]
+            )
         } else {
-            let parent = (*self.selection).select_from(&self.population);
-            (*parent.genotype).clone()
+            let parent = (*self.selection).select_from(&self.population, rng);
+            ((*parent.genotype).clone(), vec![parent.genotype.clone()])
         };
 
-        if rand::thread_rng().gen::<f32>() < self.mutation_prob {
-            self.config.mutate(&mut genotype)
+        if rng.gen::<f32>() < self.mutation_prob {
+            self.config.mutate(&mut genotype, rng)
         }
 
-        genotype
+        (genotype, parents)
     }
 
     fn next_individual(&mut self) -> Individual<G, P> {
         if (*self.selection).preserve_next() {
             // Copy existing individual without changes to the next generation
-            (*(*self.selection).select_from(&self.population)).clone()
+            (*(*self.selection).select_from(&self.population, &mut self.rng)).clone()
         } else {
             // Use selection, recombination and mutation to create new individual
-            Individual::new(self.new_genotype())
+            Individual::new(self.new_genotype().0)
         }
     }
 
@@ -465,24 +792,137 @@ impl<G: Genotype, P: Phenotype> EvolutionaryAlgorithm<G, P> {
             new_indivs.push(self.next_individual());
         }
 
-        self.population.new_generation(new_indivs);
+        if self.nsga2 {
+            self.population.add_offspring(new_indivs);
+        } else {
+            self.population.new_generation(new_indivs);
+        }
     }
 
+    // Breeds offspring for steady-state evolution and adds these to the population. Returns the
+    // parents of each offspring.
+    fn breed_children(&mut self, num_offspring: usize) -> Vec<Vec<MyRef<G>>> {
+        let mut offspring = Vec::with_capacity(num_offspring);
+        let mut parents = Vec::with_capacity(num_offspring);
+
+        (*self.selection).start_selection(&self.population);
+
+        for _ in 0..num_offspring {
+            let (genotype, genotype_parents) = self.new_genotype();
+            offspring.push(Individual::new(genotype));
+            parents.push(genotype_parents);
+        }
+
+        self.population.add_children(offspring);
+
+        parents
+    }
+
+    /// Creates and evaluates new individuals. Normally, this creates a new generation. With
+    /// steady-state evolution, only a few offspring are created, so that several steps make up
+    /// a generation. See [Self::step_generation].
     pub fn step(&mut self) {
+        let mut parents = None;
+
         if self.population.size() == 0 {
             self.populate();
+        } else if let Some(steady_state) = &self.steady_state {
+            parents = Some(self.breed_children(steady_state.num_offspring()));
         } else {
             self.breed();
         }
 
         self.grow();
         self.evaluate();
+
+        if self.nsga2 {
+            nsga2::select_survivors(&mut self.population, self.pop_size);
+        }
+        if let (Some(steady_state), Some(parents)) = (&self.steady_state, parents) {
+            steady_state.replace(&mut self.population, self.pop_size, parents, &mut self.rng);
+        }
+    }
+
+    /// Steps until the next generation has been reached, or the initial population has been
+    /// created and evaluated. Without steady-state evolution, this is the same as [Self::step].
+    pub fn step_generation(&mut self) {
+        if self.population.size() == 0 || self.steady_state.is_none() {
+            self.step();
+            return;
+        }
+
+        let generation = self.num_generations();
+        while self.num_generations() == generation {
+            self.step();
+        }
+    }
+
+    /// Returns the individuals whose objectives are not dominated by those of any other
+    /// individual. Only multi-objective evaluators set objectives, so it is empty otherwise.
+    pub fn pareto_front(&self) -> Vec<&Individual<G, P>> {
+        nsga2::pareto_front(self.population.iter())
+    }
+
+    /// Returns the indices of the given number of best individuals, best first. With NSGA-II,
+    /// these are ranked using the crowded-comparison operator, otherwise by fitness.
+    pub fn best_individuals(&self, num: usize) -> Vec<usize> {
+        let individuals = &self.population.individuals;
+        let mut ranking = if self.nsga2 {
+            nsga2::crowded_order(individuals)
+        } else {
+            let mut ranking: Vec<usize> = (0..individuals.len()).collect();
+            ranking.sort_by(|&a, &b| fitness_cmp(individuals[b].fitness, individuals[a].fitness));
+            ranking
+        };
+        ranking.truncate(num);
+        ranking
+    }
+
+    /// Adds individuals from elsewhere, e.g. another island, to the evaluated population. They
+    /// replace the least fit individuals, or with NSGA-II, compete with all individuals for
+    /// survival.
+    pub fn immigrate(&mut self, immigrants: Vec<Individual<G, P>>) {
+        if self.nsga2 {
+            self.population.individuals.extend(immigrants);
+            nsga2::select_survivors(&mut self.population, self.pop_size);
+        } else {
+            self.population.replace_worst(immigrants);
+        }
     }
 
     pub fn get_population_stats(&self) -> Option<PopulationStats<G, P>> {
         self.population.get_stats()
     }
 
+    /// Returns the current state of the run, so that it can be resumed later using [Self::restore].
+    pub fn state(&self) -> EaState<G, P> {
+        EaState {
+            population: self.population.clone(),
+            num_evaluations: self.evaluator.num_evaluations,
+            seed: self.seed,
+            rng: self.rng.clone(),
+        }
+    }
+
+    /// Resumes a run from the given state. Afterwards, the run continues exactly as the one whose
+    /// state it was, provided that both algorithms were configured identically.
+    pub fn restore(&mut self, state: EaState<G, P>) -> Result<(), String> {
+        let size = state.population.size();
+        if size != 0 && size != self.pop_size {
+            return Err(format!(
+                "Population of {} individuals does not match population size {}",
+                size, self.pop_size
+            ));
+        }
+
+        self.population = state.population;
+        self.evaluator.num_evaluations = state.num_evaluations;
+        self.seed = state.seed;
+        self.rng = state.rng;
+
+        Ok(())
+    }
+
     pub fn get_stats(&self) -> OptimizationStats {
         OptimizationStats {
             num_generations: self.population.generation,
@@ -492,4 +932,71 @@ impl<G: Genotype, P: Phenotype> EvolutionaryAlgorithm<G, P> {
 }
 
 pub mod selection;
-pub mod binary;
\ No newline at end of file
+pub mod binary;
+pub mod nsga2;
+pub mod island;
+pub mod steady_state;
+#[cfg(test)]
+mod testing;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::testing::{Numbers, NumbersConfig, NumbersExpressor};
+
+    // Scores each phenotype by the number of phenotypes in the population with a lower value
+    #[derive(Debug)]
+    struct RankEvaluator;
+
+    fn rank(phenotype: &Numbers, phenotypes: &[&Numbers]) -> f32 {
+        phenotypes.iter().filter(|other| other.0[0] < phenotype.0[0]).count() as f32
+    }
+
+    impl Evaluator<Numbers> for RankEvaluator {
+        fn evaluate(&mut self, _phenotype: &Numbers) -> f32 {
+            panic!("Interaction-based evaluators should only be used for bulk evaluation")
+        }
+
+        fn is_interaction_based(&self) -> bool {
+            true
+        }
+
+        fn bulk_evaluate(&mut self, phenotypes: &[&Numbers]) -> Vec<f32> {
+            phenotypes.iter().map(|phenotype| rank(phenotype, phenotypes)).collect()
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    #[test]
+    fn bulk_evaluation_bypasses_fitness_cache() {
+        let mut ea = EvolutionaryAlgorithm::new(
+            10,
+            Box::new(NumbersConfig { len: 1 }),
+            Box::new(NumbersExpressor),
+            Box::new(RankEvaluator),
+            Box::new(selection::TournamentSelection::new(2))
+        ).set_seed(0).enable_fitness_cache();
+
+        for _ in 0..3 {
+            ea.step();
+        }
+
+        // The whole population is re-evaluated each generation, and nothing is cached
+        assert_eq!(ea.num_evaluations(), 30);
+        assert!(ea.population().fitness_cache.as_ref().unwrap().is_empty());
+
+        // Fitness reflects the current population, rather than the one it was first seen in
+        let phenotypes: Vec<&Numbers> = ea.population().iter().map(
+            |indiv| indiv.phenotype().unwrap()
+        ).collect();
+        for (indiv, phenotype) in ea.population().iter().zip(&phenotypes) {
+            assert_eq!(indiv.fitness(), Some(rank(phenotype, &phenotypes)));
+        }
+    }
+}
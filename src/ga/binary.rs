@@ -1,9 +1,10 @@
 use super::{Genotype, Mutation, Recombination};
 use bit_vec::BitVec;
-use rand::{self, Rng};
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
 use std::{clone, cmp};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BinaryChromosome {
     pub bits: BitVec,
 }
@@ -24,11 +25,12 @@ pub struct BinaryUniformRecombination {
 }
 
 impl BinaryChromosome {
-    pub fn new(size: usize) -> BinaryChromosome {
+    /// Creates a chromosome with random bits.
+    pub fn new(size: usize, rng: &mut dyn RngCore) -> BinaryChromosome {
         let mut bits = BitVec::with_capacity(size);
 
         for _ in 0..size {
-            bits.push(rand::random());
+            bits.push(rng.gen());
         }
 
         BinaryChromosome {
@@ -70,7 +72,7 @@ impl BinaryBitMutation {
 impl Mutation for BinaryBitMutation {
     type Genotype = BinaryChromosome;
 
-    fn mutate(&self, target: &mut Self::Genotype) {
+    fn mutate(&self, target: &mut Self::Genotype, rng: &mut dyn RngCore) {
         // Instead of checking for each bit individually if it should be flipped, this function
         // calculates which bits should be flipped. It calculates which bit to mutate next as
         // follows:
@@ -88,7 +90,7 @@ impl Mutation for BinaryBitMutation {
         let denom = (1.0 - self.mutate_prob).ln();
         let mut i = 0;
         loop {
-            let num = (1.0 - rand::thread_rng().gen::<f32>()).ln();
+            let num = (1.0 - rng.gen::<f32>()).ln();
 
             // Note: the cast rounds towards zero and maps the infinity float value and other
             // values that are "too big" to the maximum integer value, which is what we want.
@@ -115,12 +117,12 @@ impl Recombination for BinaryNPointBitCrossover {
     type Genotype = BinaryChromosome;
 
     fn recombine(
-        &self, parent1: &Self::Genotype, parent2: &Self::Genotype
+        &self, parent1: &Self::Genotype, parent2: &Self::Genotype, rng: &mut dyn RngCore
     ) -> Self::Genotype {
 
         let range = cmp::min(parent1.bits.len(), parent2.bits.len());
         let mut points: Vec<usize> = (0..self.n).map(
-            |_| rand::thread_rng().gen_range(1..range)
+            |_| rng.gen_range(1..range)
         ).collect();
         points[..].sort_unstable();
 
@@ -150,6 +152,7 @@ impl BinaryUniformRecombination {
     /// equal probability from both parents. As bias increases, the one of the bits from one
     /// parent are increasingly favoured. As bias approaches 1, all bits are selected from one
     /// parent which means there is no recombination.
+    #[allow(clippy::manual_range_contains)]
     pub fn new(bias: f32) -> Self {
         if bias < 0.0 || bias >= 1.0 {
             panic!("Bias out of range");
@@ -165,13 +168,13 @@ impl Recombination for BinaryUniformRecombination {
     type Genotype = BinaryChromosome;
 
     fn recombine(
-        &self, parent1: &Self::Genotype, parent2: &Self::Genotype
+        &self, parent1: &Self::Genotype, parent2: &Self::Genotype, rng: &mut dyn RngCore
     ) -> Self::Genotype {
         let mut child = parent1.clone();
         let limit = 0.5 * (1.0 + self.bias);
 
         for i in 0..child.bits.len() {
-            if rand::thread_rng().gen::<f32>() >= limit {
+            if rng.gen::<f32>() >= limit {
                 child.bits.set(i, parent2.bits.get(i).unwrap());
             }
         }
@@ -0,0 +1,394 @@
+// The island model runs several evolutionary algorithms side by side, each evolving its own
+// sub-population. Every so many generations, some individuals migrate between islands. In
+// between, the islands evolve independently, which keeps the overall population diverse and
+// counters premature convergence.
+use wasm_bindgen::prelude::*;
+use super::{
+    EaState, EvolutionaryAlgorithm, Genotype, Individual, OptimizationStats, Phenotype,
+    PopulationStats
+};
+use rand::{Rng, SeedableRng};
+use rand::seq::index;
+use rand_pcg::Pcg64Mcg;
+use serde::{Deserialize, Serialize};
+
+/// Determines to which islands the migrants of each island move.
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Topology {
+    // The islands form a ring, and migrants move to the next island
+    Ring,
+    // Migrants move to all other islands
+    FullyConnected,
+    // Migrants move to another island that is picked at random each migration
+    Random,
+}
+
+/// Determines which individuals of an island migrate.
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum MigrantSelection {
+    Best,
+    Random,
+}
+
+#[derive(Debug)]
+pub struct IslandModel<G: Genotype, P: Phenotype> {
+    islands: Vec<EvolutionaryAlgorithm<G, P>>,
+    topology: Topology,
+    migrant_selection: MigrantSelection,
+    num_migrants: usize,
+    // The number of generations between migrations
+    migration_interval: u32,
+    // The seed that the random number generator for migration was initialised with
+    seed: u64,
+    rng: Pcg64Mcg,
+}
+
+/// The state of an island model that changes while it runs. See [EaState].
+#[derive(Serialize, Deserialize)]
+pub struct IslandModelState<G: Genotype, P: Phenotype> {
+    islands: Vec<EaState<G, P>>,
+    seed: u64,
+    rng: Pcg64Mcg,
+}
+
+impl<G: Genotype, P: Phenotype> IslandModel<G, P> {
+    /// Creates an island model with the given islands. By default, the best individual of each
+    /// island migrates to the next island in a ring every ten generations.
+    pub fn new(islands: Vec<EvolutionaryAlgorithm<G, P>>) -> Self {
+        assert!(!islands.is_empty(), "Island model requires at least one island");
+        let seed = rand::thread_rng().gen();
+
+        IslandModel {
+            islands,
+            topology: Topology::Ring,
+            migrant_selection: MigrantSelection::Best,
+            num_migrants: 1,
+            migration_interval: 10,
+            seed,
+            rng: Pcg64Mcg::seed_from_u64(seed),
+        }
+    }
+
+    /// Seeds the random number generator used for migration. The islands have their own
+    /// generators, which should be seeded separately for runs to be reproducible.
+    pub fn set_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self.rng = Pcg64Mcg::seed_from_u64(seed);
+        self
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn set_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    pub fn set_migrant_selection(mut self, migrant_selection: MigrantSelection) -> Self {
+        self.migrant_selection = migrant_selection;
+        self
+    }
+
+    pub fn set_num_migrants(mut self, num_migrants: usize) -> Self {
+        self.num_migrants = num_migrants;
+        self
+    }
+
+    pub fn set_migration_interval(mut self, migration_interval: u32) -> Self {
+        assert!(migration_interval > 0, "Migration interval should be at least one");
+        self.migration_interval = migration_interval;
+        self
+    }
+
+    pub fn num_islands(&self) -> usize {
+        self.islands.len()
+    }
+
+    pub fn island(&self, index: usize) -> &EvolutionaryAlgorithm<G, P> {
+        &self.islands[index]
+    }
+
+    pub fn islands(&self) -> &[EvolutionaryAlgorithm<G, P>] {
+        &self.islands
+    }
+
+    pub fn islands_mut(&mut self) -> &mut [EvolutionaryAlgorithm<G, P>] {
+        &mut self.islands
+    }
+
+    /// Iterates over the individuals of all islands.
+    pub fn individuals(&self) -> impl Iterator<Item = &Individual<G, P>> {
+        self.islands.iter().flat_map(|island| island.population().iter())
+    }
+
+    // All islands advance in lockstep, so they are always at the same generation
+    pub fn num_generations(&self) -> u32 {
+        self.islands[0].num_generations()
+    }
+
+    pub fn num_evaluations(&self) -> u32 {
+        self.islands.iter().map(|island| island.num_evaluations()).sum()
+    }
+
+    /// Advances all islands by one generation, after which individuals migrate when it is time.
+    pub fn step(&mut self) {
+        for island in self.islands.iter_mut() {
+            island.step_generation();
+        }
+
+        let num_generations = self.num_generations();
+        if self.islands.len() > 1 && num_generations.is_multiple_of(self.migration_interval) {
+            self.migrate();
+        }
+    }
+
+    // Returns the indices of the individuals of the island that should migrate
+    fn select_migrants(&mut self, island: usize) -> Vec<usize> {
+        let population = self.islands[island].population();
+        let num_migrants = self.num_migrants.min(population.size());
+
+        match self.migrant_selection {
+            MigrantSelection::Best => self.islands[island].best_individuals(num_migrants),
+            MigrantSelection::Random => {
+                index::sample(&mut self.rng, population.size(), num_migrants).into_vec()
+            },
+        }
+    }
+
+    // Returns the islands that the migrants of the given island move to
+    fn destinations(&mut self, island: usize) -> Vec<usize> {
+        let n = self.islands.len();
+
+        match self.topology {
+            Topology::Ring => vec![(island + 1) % n],
+            Topology::FullyConnected => (0..n).filter(|&i| i != island).collect(),
+            Topology::Random => vec![(island + self.rng.gen_range(1..n)) % n],
+        }
+    }
+
+    /// Copies migrants from each island to its destinations. All migrants are selected before
+    /// any of them arrive, so that the outcome does not depend on the order of the islands.
+    pub fn migrate(&mut self) {
+        let mut arrivals: Vec<Vec<Individual<G, P>>> = vec![Vec::new(); self.islands.len()];
+
+        for island in 0..self.islands.len() {
+            let migrants = self.select_migrants(island);
+            for destination in self.destinations(island) {
+                let population = self.islands[island].population();
+                arrivals[destination].extend(
+                    migrants.iter().map(|&i| population.get_individual(i).clone())
+                );
+            }
+        }
+
+        for (island, immigrants) in self.islands.iter_mut().zip(arrivals) {
+            island.immigrate(immigrants);
+        }
+    }
+
+    /// Returns the individuals that are not dominated by any individual on any island.
+    pub fn pareto_front(&self) -> Vec<&Individual<G, P>> {
+        super::nsga2::pareto_front(self.individuals())
+    }
+
+    pub fn get_island_stats(&self, index: usize) -> Option<PopulationStats<G, P>> {
+        self.islands[index].get_population_stats()
+    }
+
+    /// Returns the statistics over the individuals of all islands.
+    pub fn get_population_stats(&self) -> Option<PopulationStats<G, P>> {
+        let mut total: Option<PopulationStats<G, P>> = None;
+        let mut sum = 0.0;
+        let mut num_islands = 0;
+
+        for stats in self.islands.iter().filter_map(|island| island.get_population_stats()) {
+            // Islands are equally large, so the overall average is the average of the islands
+            sum += stats.avg_fitness;
+            num_islands += 1;
+            if total.as_ref().is_none_or(|best| stats.max_fitness > best.max_fitness) {
+                total = Some(stats);
+            }
+        }
+
+        total.map(|stats| PopulationStats {
+            avg_fitness: sum / num_islands as f32,
+            ..stats
+        })
+    }
+
+    pub fn get_stats(&self) -> OptimizationStats {
+        OptimizationStats {
+            num_generations: self.num_generations(),
+            num_evaluations: self.num_evaluations(),
+        }
+    }
+
+    pub fn state(&self) -> IslandModelState<G, P> {
+        IslandModelState {
+            islands: self.islands.iter().map(|island| island.state()).collect(),
+            seed: self.seed,
+            rng: self.rng.clone(),
+        }
+    }
+
+    pub fn restore(&mut self, state: IslandModelState<G, P>) -> Result<(), String> {
+        if state.islands.len() != self.islands.len() {
+            return Err(format!(
+                "State of {} islands does not match model of {} islands",
+                state.islands.len(), self.islands.len()
+            ));
+        }
+
+        for (island, island_state) in self.islands.iter_mut().zip(state.islands) {
+            island.restore(island_state)?;
+        }
+        self.seed = state.seed;
+        self.rng = state.rng;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::MyRef;
+    use super::super::testing::{individual, numbers_ea, population, values, Numbers};
+
+    // Returns a model of three islands of four individuals each. The fitness of the individuals
+    // on island i ranges from 10 * i to 10 * i + 3
+    fn model(topology: Topology) -> IslandModel<Numbers, Numbers> {
+        let islands = (0..3).map(|island| {
+            let mut ea = numbers_ea(4, 1, island);
+            ea.population = population(
+                (0..4).map(|i| individual(&[10 * island as i32 + i])).collect()
+            );
+            ea
+        }).collect();
+
+        IslandModel::new(islands).set_topology(topology).set_seed(1)
+    }
+
+    // Returns the sorted values of the individuals on each island
+    fn island_values(model: &IslandModel<Numbers, Numbers>) -> Vec<Vec<i32>> {
+        model.islands().iter().map(|island| {
+            let mut values: Vec<i32> = values(island.population()).concat();
+            values.sort_unstable();
+            values
+        }).collect()
+    }
+
+    #[test]
+    fn ring_migration() {
+        let mut model = model(Topology::Ring);
+        model.migrate();
+
+        // The best individual of each island replaces the worst of the next
+        assert_eq!(island_values(&model), vec![
+            vec![1, 2, 3, 23], vec![3, 11, 12, 13], vec![13, 21, 22, 23]
+        ]);
+    }
+
+    #[test]
+    fn fully_connected_migration() {
+        let mut model = model(Topology::FullyConnected).set_num_migrants(2);
+        model.migrate();
+
+        assert_eq!(island_values(&model), vec![
+            vec![12, 13, 22, 23], vec![2, 3, 22, 23], vec![2, 3, 12, 13]
+        ]);
+    }
+
+    #[test]
+    fn random_migration() {
+        for seed in 0..10 {
+            let mut model = model(Topology::Random).set_seed(seed);
+            model.migrate();
+
+            // Each island sends its best individual to one of the other islands
+            let mut arrivals: Vec<i32> = island_values(&model).iter().enumerate().flat_map(
+                |(island, values)| values.iter().copied().filter(
+                    move |&value| value / 10 != island as i32
+                ).collect::<Vec<i32>>()
+            ).collect();
+            arrivals.sort_unstable();
+            assert_eq!(arrivals, vec![3, 13, 23]);
+        }
+    }
+
+    #[test]
+    fn random_migrants() {
+        let mut model = model(Topology::Ring).set_migrant_selection(MigrantSelection::Random);
+        model.migrate();
+
+        for (island, values) in island_values(&model).iter().enumerate() {
+            let previous = (island + 2) % 3;
+            let arrivals: Vec<i32> = values.iter().copied().filter(
+                |&value| value / 10 == previous as i32
+            ).collect();
+            assert_eq!(arrivals.len(), 1);
+            // The worst individual was replaced
+            assert!(!values.contains(&(10 * island as i32)));
+        }
+    }
+
+    #[test]
+    fn surplus_immigrants_keep_the_fittest() {
+        let mut model = model(Topology::FullyConnected).set_num_migrants(3);
+        model.migrate();
+
+        // Six immigrants arrive at each island of four individuals
+        assert_eq!(island_values(&model), vec![
+            vec![13, 21, 22, 23], vec![3, 21, 22, 23], vec![3, 11, 12, 13]
+        ]);
+    }
+
+    #[test]
+    fn best_migrants_with_nsga2() {
+        let mut ea = numbers_ea(5, 2, 0).enable_nsga2();
+        ea.population = population(vec![
+            individual(&[0, 4]), individual(&[1, 3]), individual(&[1, 1]), individual(&[2, 2]),
+            individual(&[4, 0]),
+        ]);
+        let mut model = IslandModel::new(vec![ea]).set_num_migrants(2);
+
+        // The extremes of the first front, not the ones with the highest first objective
+        assert_eq!(model.select_migrants(0), vec![0, 4]);
+    }
+
+    #[test]
+    fn island_and_total_stats() {
+        let model = model(Topology::Ring);
+
+        let stats = model.get_island_stats(1).unwrap();
+        assert_eq!((stats.max_fitness, stats.avg_fitness), (13.0, 11.5));
+
+        let stats = model.get_population_stats().unwrap();
+        assert_eq!((stats.max_fitness, stats.avg_fitness), (23.0, 11.5));
+        assert_eq!(stats.best_indiv.fitness(), Some(23.0));
+    }
+
+    #[test]
+    fn migrates_every_interval() {
+        let islands = (0..2).map(|island| numbers_ea(6, 1, island)).collect();
+        let mut model = IslandModel::new(islands).set_migration_interval(3).set_seed(3);
+        // Migrants share their genotype with the individual they were copied from, whereas
+        // breeding always creates new genotypes
+        let shares_genotype = |model: &IslandModel<Numbers, Numbers>| {
+            model.island(0).population().iter().any(|a| model.island(1).population().iter().any(
+                |b| MyRef::ptr_eq(a.genotype(), b.genotype())
+            ))
+        };
+
+        for generation in 1..=9 {
+            model.step();
+            assert_eq!(model.num_generations(), generation);
+            assert_eq!(model.individuals().count(), 12);
+            assert_eq!(shares_genotype(&model), generation % 3 == 0);
+        }
+    }
+}
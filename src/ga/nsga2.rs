@@ -0,0 +1,303 @@
+// NSGA-II, a multi-objective evolutionary algorithm. Instead of a single fitness value, each
+// individual has a vector of objectives, which are all maximised. Individuals are ranked by
+// non-dominated sorting: the first front contains all individuals that are not dominated by any
+// other, the second front those that are only dominated by individuals in the first front, etc.
+// Within a front, individuals in sparsely populated regions are preferred, as measured by their
+// crowding distance.
+//
+// See: Deb et al., "A fast and elitist multiobjective genetic algorithm: NSGA-II", 2002
+use super::{Genotype, Phenotype, Individual, Population, Selection};
+use rand::{Rng, RngCore};
+use std::cmp::Ordering;
+
+#[derive(Debug)]
+pub struct Nsga2Selection {
+    // The index of the front of each individual
+    ranks: Vec<usize>,
+    crowding_distances: Vec<f32>,
+}
+
+/// Returns true iff the first objective vector is at least as good as the second in every
+/// objective, and better in at least one of them.
+pub fn dominates(a: &[f32], b: &[f32]) -> bool {
+    a.iter().zip(b.iter()).all(|(x, y)| x >= y) && a.iter().zip(b.iter()).any(|(x, y)| x > y)
+}
+
+/// Sorts the objective vectors into fronts of mutually non-dominating vectors. Returns the indices
+/// of the vectors in each front, best front first.
+pub fn non_dominated_sort(objectives: &[&[f32]]) -> Vec<Vec<usize>> {
+    let n = objectives.len();
+    // For each vector, the vectors it dominates and the number of vectors that dominate it
+    let mut dominated: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut num_dominating = vec![0; n];
+
+    for i in 0..n {
+        for j in i + 1..n {
+            if dominates(objectives[i], objectives[j]) {
+                dominated[i].push(j);
+                num_dominating[j] += 1;
+            } else if dominates(objectives[j], objectives[i]) {
+                dominated[j].push(i);
+                num_dominating[i] += 1;
+            }
+        }
+    }
+
+    let mut fronts = Vec::new();
+    let mut front: Vec<usize> = (0..n).filter(|&i| num_dominating[i] == 0).collect();
+    while !front.is_empty() {
+        let mut next_front = Vec::new();
+        for &i in front.iter() {
+            for &j in dominated[i].iter() {
+                num_dominating[j] -= 1;
+                if num_dominating[j] == 0 {
+                    next_front.push(j);
+                }
+            }
+        }
+        fronts.push(front);
+        front = next_front;
+    }
+
+    fronts
+}
+
+/// Returns the crowding distance of each vector in the front, in the same order. Vectors at the
+/// extremes of any objective have an infinite distance.
+pub fn crowding_distances(objectives: &[&[f32]], front: &[usize]) -> Vec<f32> {
+    let mut distances = vec![0.0; front.len()];
+    if front.len() <= 2 {
+        distances.iter_mut().for_each(|d| *d = f32::INFINITY);
+        return distances;
+    }
+
+    let num_objectives = objectives[front[0]].len();
+    let mut order: Vec<usize> = (0..front.len()).collect();
+    let columns = (0..num_objectives).map(
+        |m| front.iter().map(|&i| objectives[i][m]).collect::<Vec<f32>>()
+    );
+    for values in columns {
+        order.sort_by(|&a, &b| values[a].partial_cmp(&values[b]).unwrap_or(Ordering::Equal));
+
+        let (first, last) = (order[0], order[order.len() - 1]);
+        distances[first] = f32::INFINITY;
+        distances[last] = f32::INFINITY;
+
+        let range = values[last] - values[first];
+        if range > 0.0 {
+            for neighbours in order.windows(3) {
+                distances[neighbours[1]] += (values[neighbours[2]] - values[neighbours[0]]) / range;
+            }
+        }
+    }
+
+    distances
+}
+
+// Returns the objectives of each individual. Individuals that have not been evaluated are
+// treated as having no objectives, which makes them equal to one another and dominated by none.
+fn population_objectives<G: Genotype, P: Phenotype>(
+    individuals: &[Individual<G, P>]
+) -> Vec<&[f32]> {
+    individuals.iter().map(|indiv| indiv.objectives().unwrap_or(&[])).collect()
+}
+
+// Returns for each individual its rank and crowding distance
+fn rank_individuals<G: Genotype, P: Phenotype>(
+    individuals: &[Individual<G, P>]
+) -> (Vec<usize>, Vec<f32>) {
+    let objectives = population_objectives(individuals);
+    let mut ranks = vec![0; individuals.len()];
+    let mut distances = vec![0.0; individuals.len()];
+
+    for (rank, front) in non_dominated_sort(&objectives).iter().enumerate() {
+        for (&i, distance) in front.iter().zip(crowding_distances(&objectives, front)) {
+            ranks[i] = rank;
+            distances[i] = distance;
+        }
+    }
+
+    (ranks, distances)
+}
+
+// The crowded-comparison operator: lower ranks are better and, within a rank, larger distances.
+fn crowded_cmp(rank_a: usize, distance_a: f32, rank_b: usize, distance_b: f32) -> Ordering {
+    rank_a.cmp(&rank_b).then(distance_b.partial_cmp(&distance_a).unwrap_or(Ordering::Equal))
+}
+
+/// Returns the indices of the individuals ordered from best to worst according to the
+/// crowded-comparison operator.
+pub(super) fn crowded_order<G: Genotype, P: Phenotype>(
+    individuals: &[Individual<G, P>]
+) -> Vec<usize> {
+    let (ranks, distances) = rank_individuals(individuals);
+    let mut order: Vec<usize> = (0..individuals.len()).collect();
+    // Stable sort, so that ties are broken consistently
+    order.sort_by(|&a, &b| crowded_cmp(ranks[a], distances[a], ranks[b], distances[b]));
+    order
+}
+
+/// Reduces the population to the given number of individuals, keeping the best ones according to
+/// the crowded-comparison operator.
+pub(super) fn select_survivors<G: Genotype, P: Phenotype>(
+    population: &mut Population<G, P>, size: usize
+) {
+    if population.individuals.len() <= size {
+        return;
+    }
+
+    let mut order = crowded_order(&population.individuals);
+    order.truncate(size);
+    order.sort_unstable();
+
+    let mut individuals = std::mem::take(&mut population.individuals);
+    let mut index = 0;
+    individuals.retain(|_| {
+        let keep = order.binary_search(&index).is_ok();
+        index += 1;
+        keep
+    });
+    population.individuals = individuals;
+}
+
+/// Returns the individuals in the first front, i.e. those that are not dominated by any other
+/// individual. Individuals with the same phenotype are only included once.
+pub fn pareto_front<'a, G: Genotype, P: Phenotype>(
+    individuals: impl Iterator<Item = &'a Individual<G, P>>
+) -> Vec<&'a Individual<G, P>> {
+    let evaluated: Vec<&Individual<G, P>> = individuals.filter(
+        |indiv| indiv.objectives().is_some()
+    ).collect();
+    let objectives: Vec<&[f32]> = evaluated.iter().map(
+        |indiv| indiv.objectives().unwrap()
+    ).collect();
+
+    let mut front: Vec<&Individual<G, P>> = Vec::new();
+    if let Some(first) = non_dominated_sort(&objectives).first() {
+        for &i in first.iter() {
+            if !front.iter().any(|other| other.phenotype() == evaluated[i].phenotype()) {
+                front.push(evaluated[i]);
+            }
+        }
+    }
+
+    front
+}
+
+impl Nsga2Selection {
+    pub fn new() -> Self {
+        Nsga2Selection {
+            ranks: Vec::new(),
+            crowding_distances: Vec::new(),
+        }
+    }
+}
+
+impl Default for Nsga2Selection {
+    fn default() -> Self {
+        Nsga2Selection::new()
+    }
+}
+
+impl<G: Genotype, P: Phenotype> Selection<G, P> for Nsga2Selection {
+
+    fn start_selection(&mut self, population: &Population<G, P>) {
+        let (ranks, distances) = rank_individuals(&population.individuals);
+
+        self.ranks = ranks;
+        self.crowding_distances = distances;
+    }
+
+    // Binary tournament using the crowded-comparison operator
+    fn select_from<'a>(
+        &mut self, population: &'a Population<G, P>, rng: &mut dyn RngCore
+    ) -> &'a Individual<G, P> {
+        let a = rng.gen_range(0..population.size());
+        let b = rng.gen_range(0..population.size());
+
+        let winner = match crowded_cmp(
+            self.ranks[a], self.crowding_distances[a], self.ranks[b], self.crowding_distances[b]
+        ) {
+            Ordering::Greater => b,
+            _ => a,
+        };
+
+        population.get_individual(winner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::EvolutionaryAlgorithm;
+    use super::super::testing::{individual, numbers_ea, population, values};
+
+    #[test]
+    fn sort_into_fronts() {
+        let objectives: Vec<&[f32]> = vec![
+            &[1.0, 5.0], &[2.0, 4.0], &[3.0, 3.0], &[1.0, 1.0], &[2.0, 2.0], &[0.0, 0.0],
+            &[3.0, 3.0],
+        ];
+
+        let mut fronts = non_dominated_sort(&objectives);
+        fronts.iter_mut().for_each(|front| front.sort_unstable());
+
+        // Equal vectors do not dominate each other, so they end up in the same front
+        assert_eq!(fronts, vec![vec![0, 1, 2, 6], vec![4], vec![3], vec![5]]);
+    }
+
+    #[test]
+    fn extremes_have_infinite_crowding_distance() {
+        let objectives: Vec<&[f32]> = vec![&[0.0, 4.0], &[1.0, 3.0], &[2.0, 2.0], &[4.0, 0.0]];
+
+        let distances = crowding_distances(&objectives, &[0, 1, 2, 3]);
+
+        assert_eq!(distances, vec![f32::INFINITY, 1.0, 1.5, f32::INFINITY]);
+        assert_eq!(crowding_distances(&objectives, &[1, 2]), vec![f32::INFINITY; 2]);
+    }
+
+    #[test]
+    fn survivors_are_selected_by_rank_then_crowding_distance() {
+        let individuals = vec![
+            individual(&[0, 4]), individual(&[1, 3]), individual(&[1, 1]), individual(&[2, 2]),
+            individual(&[4, 0]),
+        ];
+
+        // The dominated individual goes first
+        let mut pop = population(individuals.clone());
+        select_survivors(&mut pop, 4);
+        assert_eq!(values(&pop), vec![vec![0, 4], vec![1, 3], vec![2, 2], vec![4, 0]]);
+
+        // Then the one in the most crowded region of the first front
+        let mut pop = population(individuals);
+        select_survivors(&mut pop, 3);
+        assert_eq!(values(&pop), vec![vec![0, 4], vec![2, 2], vec![4, 0]]);
+    }
+
+    #[test]
+    fn run_is_elitist() {
+        let mut ea = numbers_ea(20, 2, 1).enable_nsga2();
+        let best_first_objective = |ea: &EvolutionaryAlgorithm<_, _>| ea.population().iter().map(
+            |indiv| indiv.objectives().unwrap()[0]
+        ).fold(f32::MIN, f32::max);
+
+        ea.step();
+        let mut best = best_first_objective(&ea);
+        for _ in 0..20 {
+            ea.step();
+            assert_eq!(ea.population().size(), 20);
+
+            // The extremes of the first front always survive
+            assert!(best_first_objective(&ea) >= best);
+            best = best_first_objective(&ea);
+        }
+
+        let front = ea.pareto_front();
+        assert!(!front.is_empty());
+        for member in front {
+            assert!(ea.population().iter().all(
+                |indiv| !dominates(indiv.objectives().unwrap(), member.objectives().unwrap())
+            ));
+        }
+    }
+}
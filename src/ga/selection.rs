@@ -1,5 +1,5 @@
 use super::{Genotype, Phenotype, Individual, Population, Selection};
-use rand::{self, Rng};
+use rand::{Rng, RngCore};
 use std::iter::FromIterator;
 
 #[derive(Debug)]
@@ -26,21 +26,23 @@ impl TournamentSelection {
     }
 
     fn select_one<'a, G: Genotype, P: Phenotype>(
-        &self, population: &'a Population<G, P>
+        &self, population: &'a Population<G, P>, rng: &mut dyn RngCore
     ) -> &'a Individual<G, P> {
         population.get_individual(
-            rand::thread_rng().gen_range(0..population.size())
+            rng.gen_range(0..population.size())
         )
     }
 }
 
 impl<G: Genotype, P: Phenotype> Selection<G, P> for TournamentSelection {
 
-    fn select_from<'a>(&mut self, population: &'a Population<G, P>) -> &'a Individual<G, P> {
-        let mut best = self.select_one(population);
+    fn select_from<'a>(
+        &mut self, population: &'a Population<G, P>, rng: &mut dyn RngCore
+    ) -> &'a Individual<G, P> {
+        let mut best = self.select_one(population, rng);
 
         for _ in 1..self.tournament_size {
-            let other = self.select_one(population);
+            let other = self.select_one(population, rng);
 
             if other.fitness > best.fitness {
                 best = other;
@@ -84,7 +86,9 @@ impl<G: Genotype, P: Phenotype> Selection<G, P> for ElitismSelection<G, P> {
         self.num_selected_elites < self.elite_size
     }
 
-    fn select_from<'a>(&mut self, population: &'a Population<G, P>) -> &'a Individual<G, P> {
+    fn select_from<'a>(
+        &mut self, population: &'a Population<G, P>, rng: &mut dyn RngCore
+    ) -> &'a Individual<G, P> {
         if self.num_selected_elites < self.elite_size {
             let individual = population.get_individual(
                 *self.ranking.get(self.num_selected_elites).expect("Elite size exceeds ranking")
@@ -94,7 +98,7 @@ impl<G: Genotype, P: Phenotype> Selection<G, P> for ElitismSelection<G, P> {
 
             individual
         } else {
-            self.wrapped_selection.select_from(population)
+            self.wrapped_selection.select_from(population, rng)
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,190 @@
+// Steady-state evolution. Instead of replacing the whole population each generation, only a few
+// offspring are created at a time. Once evaluated, each takes the place of an existing
+// individual, as determined by the replacement policy. Good offspring can therefore become
+// parents right away, which often reduces the number of evaluations needed.
+use wasm_bindgen::prelude::*;
+use super::{Genotype, Individual, MyRef, Phenotype, Population};
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Determines which individual is replaced by each offspring.
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Replacement {
+    // The least fit individual
+    Worst,
+    // A randomly chosen individual
+    Random,
+    // The least fit parent, but only when the offspring is fitter. Otherwise it is discarded
+    ParentIfBetter,
+    // The least fit of a few randomly chosen individuals
+    InverseTournament,
+}
+
+#[derive(Debug)]
+pub struct SteadyState {
+    num_offspring: usize,
+    replacement: Replacement,
+    // Only used by Replacement::InverseTournament
+    tournament_size: usize,
+}
+
+fn fitness_cmp<G: Genotype, P: Phenotype>(a: &Individual<G, P>, b: &Individual<G, P>) -> Ordering {
+    a.fitness.unwrap_or(f32::MIN).partial_cmp(&b.fitness.unwrap_or(f32::MIN))
+        .unwrap_or(Ordering::Equal)
+}
+
+// Returns the index of the least fit of the candidates
+fn least_fit<G: Genotype, P: Phenotype>(
+    individuals: &[Individual<G, P>], candidates: impl Iterator<Item = usize>
+) -> Option<usize> {
+    candidates.min_by(|&a, &b| fitness_cmp(&individuals[a], &individuals[b]))
+}
+
+impl SteadyState {
+    pub fn new(num_offspring: usize, replacement: Replacement, tournament_size: usize) -> Self {
+        assert!(num_offspring > 0, "Steady-state evolution requires at least one offspring");
+        assert!(tournament_size > 0, "Tournament size should be at least one");
+
+        SteadyState {
+            num_offspring,
+            replacement,
+            tournament_size,
+        }
+    }
+
+    pub fn num_offspring(&self) -> usize {
+        self.num_offspring
+    }
+
+    pub fn replacement(&self) -> Replacement {
+        self.replacement
+    }
+
+    // Returns the index of the individual that the offspring should replace, if any
+    fn victim<G: Genotype, P: Phenotype>(
+        &self, individuals: &[Individual<G, P>], offspring: &Individual<G, P>,
+        parents: &[MyRef<G>], rng: &mut dyn RngCore
+    ) -> Option<usize> {
+        let size = individuals.len();
+
+        match self.replacement {
+            Replacement::Worst => least_fit(individuals, 0..size),
+            Replacement::Random => Some(rng.gen_range(0..size)),
+            Replacement::ParentIfBetter => {
+                // Parents may have been replaced already by earlier offspring of the same step
+                let parent_indices = (0..size).filter(|&i| parents.iter().any(
+                    |parent| MyRef::ptr_eq(&individuals[i].genotype, parent)
+                ));
+                least_fit(individuals, parent_indices).filter(
+                    |&i| fitness_cmp(offspring, &individuals[i]) == Ordering::Greater
+                )
+            },
+            Replacement::InverseTournament => least_fit(
+                individuals, (0..self.tournament_size).map(|_| rng.gen_range(0..size))
+            ),
+        }
+    }
+
+    /// Reduces the population to the given size by letting the evaluated offspring, which
+    /// follow the existing individuals, replace these according to the replacement policy. For
+    /// each offspring, the genotypes of its parents should be given.
+    pub(super) fn replace<G: Genotype, P: Phenotype>(
+        &self, population: &mut Population<G, P>, size: usize, parents: Vec<Vec<MyRef<G>>>,
+        rng: &mut dyn RngCore
+    ) {
+        let offspring = population.individuals.split_off(size);
+        assert_eq!(offspring.len(), parents.len());
+
+        for (child, parents) in offspring.into_iter().zip(parents) {
+            let individuals = &population.individuals;
+            if let Some(victim) = self.victim(individuals, &child, &parents, rng) {
+                population.individuals[victim] = child;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::testing::{individual, numbers_ea, population, values, Numbers};
+    use rand::SeedableRng;
+    use rand_pcg::Pcg64Mcg;
+
+    // Lets a single offspring with the given value replace one of the individuals with the given
+    // values, whose parents are the individuals at the given indices
+    fn replace(
+        replacement: Replacement, tournament_size: usize, values_before: &[i32], offspring: i32,
+        parents: &[usize]
+    ) -> Vec<i32> {
+        let mut pop = population(values_before.iter().map(|&value| individual(&[value])).collect());
+        let parents: Vec<MyRef<Numbers>> = parents.iter().map(
+            |&i| pop.get_individual(i).genotype().clone()
+        ).collect();
+        pop.individuals.push(individual(&[offspring]));
+
+        let steady_state = SteadyState::new(1, replacement, tournament_size);
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        steady_state.replace(&mut pop, values_before.len(), vec![parents], &mut rng);
+
+        values(&pop).concat()
+    }
+
+    #[test]
+    fn replace_worst() {
+        assert_eq!(replace(Replacement::Worst, 1, &[5, 1, 7], 4, &[0, 2]), vec![5, 4, 7]);
+        // Also when the offspring is less fit
+        assert_eq!(replace(Replacement::Worst, 1, &[5, 1, 7], 0, &[0, 2]), vec![5, 0, 7]);
+    }
+
+    #[test]
+    fn replace_random() {
+        let after = replace(Replacement::Random, 1, &[5, 1, 7], 4, &[0, 2]);
+
+        assert_eq!(after.len(), 3);
+        assert_eq!(after.iter().filter(|&&value| value == 4).count(), 1);
+        assert_eq!(after.iter().filter(|value| [5, 1, 7].contains(value)).count(), 2);
+    }
+
+    #[test]
+    fn replace_parent_if_better() {
+        // The least fit parent is replaced, even though another individual is less fit
+        assert_eq!(replace(Replacement::ParentIfBetter, 1, &[5, 1, 7], 6, &[0, 2]), vec![6, 1, 7]);
+        // Offspring that is not fitter than its parents is discarded
+        assert_eq!(replace(Replacement::ParentIfBetter, 1, &[5, 1, 7], 4, &[0, 2]), vec![5, 1, 7]);
+    }
+
+    #[test]
+    fn replace_inverse_tournament() {
+        // With a large tournament, the least fit individual is all but certain to take part
+        assert_eq!(
+            replace(Replacement::InverseTournament, 50, &[5, 1, 7], 4, &[0, 2]), vec![5, 4, 7]
+        );
+        // With a tournament of one, a random individual is replaced
+        let after = replace(Replacement::InverseTournament, 1, &[5, 1, 7], 4, &[0, 2]);
+        assert_eq!(after.iter().filter(|&&value| value == 4).count(), 1);
+    }
+
+    #[test]
+    fn generation_ends_once_population_size_offspring_created() {
+        let mut ea = numbers_ea(10, 1, 0).enable_steady_state(3, Replacement::Worst, 2);
+
+        // The initial population is the first generation
+        ea.step();
+        assert_eq!((ea.num_generations(), ea.num_evaluations()), (1, 10));
+
+        for _ in 0..3 {
+            ea.step();
+        }
+        assert_eq!((ea.num_generations(), ea.num_evaluations()), (1, 19));
+
+        // The offspring that exceed the population size count towards the next generation
+        ea.step();
+        assert_eq!((ea.num_generations(), ea.num_evaluations()), (2, 22));
+        ea.step_generation();
+        assert_eq!((ea.num_generations(), ea.num_evaluations()), (3, 31));
+        assert_eq!(ea.population().size(), 10);
+    }
+}
@@ -0,0 +1,116 @@
+// A minimal problem for testing the evolutionary algorithms. The genotype is a list of numbers,
+// which is also its phenotype. Each number is one objective, and the fitness is their sum.
+use super::*;
+
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct Numbers(pub Vec<i32>);
+
+impl Genotype for Numbers {}
+
+impl Phenotype for Numbers {}
+
+#[derive(Debug)]
+pub struct NumbersExpressor;
+
+impl Expressor<Numbers, Numbers> for NumbersExpressor {
+    fn express(&mut self, genotype: &Numbers) -> Numbers {
+        genotype.clone()
+    }
+}
+
+#[derive(Debug)]
+pub struct NumbersEvaluator {
+    pub num_objectives: usize,
+}
+
+impl Evaluator<Numbers> for NumbersEvaluator {
+    fn evaluate(&mut self, phenotype: &Numbers) -> f32 {
+        phenotype.0.iter().sum::<i32>() as f32
+    }
+
+    fn num_objectives(&self) -> usize {
+        self.num_objectives
+    }
+
+    fn evaluate_objectives(&mut self, phenotypes: &[&Numbers]) -> Vec<Vec<f32>> {
+        phenotypes.iter().map(
+            |phenotype| phenotype.0.iter().map(|&value| value as f32).collect()
+        ).collect()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct NumbersConfig {
+    pub len: usize,
+}
+
+impl GenotypeFactory<Numbers> for NumbersConfig {
+    fn create(&self, rng: &mut dyn RngCore) -> Numbers {
+        Numbers((0..self.len).map(|_| rng.gen_range(0..100)).collect())
+    }
+}
+
+impl GenotypeManipulation<Numbers> for NumbersConfig {
+    fn mutate(&self, target: &mut Numbers, rng: &mut dyn RngCore) {
+        let index = rng.gen_range(0..self.len);
+        target.0[index] += if rng.gen() { 1 } else { -1 };
+    }
+
+    fn recombine(&self, parent1: &Numbers, parent2: &Numbers, rng: &mut dyn RngCore) -> Numbers {
+        Numbers(parent1.0.iter().zip(parent2.0.iter()).map(
+            |(&a, &b)| if rng.gen() { a } else { b }
+        ).collect())
+    }
+}
+
+impl GenotypeConfig<Numbers> for NumbersConfig {}
+
+// Returns a seeded EA whose genotypes contain the given number of numbers
+pub fn numbers_ea(
+    pop_size: usize, num_objectives: usize, seed: u64
+) -> EvolutionaryAlgorithm<Numbers, Numbers> {
+    EvolutionaryAlgorithm::new(
+        pop_size,
+        Box::new(NumbersConfig { len: num_objectives }),
+        Box::new(NumbersExpressor),
+        Box::new(NumbersEvaluator { num_objectives }),
+        Box::new(selection::TournamentSelection::new(2))
+    ).set_seed(seed)
+}
+
+// Returns an evaluated individual whose objectives are the given numbers
+pub fn individual(values: &[i32]) -> Individual<Numbers, Numbers> {
+    let numbers = Numbers(values.to_vec());
+    let objectives: Vec<f32> = values.iter().map(|&value| value as f32).collect();
+
+    Individual {
+        genotype: MyRef::new(numbers.clone()),
+        phenotype: Some(MyRef::new(numbers)),
+        fitness: Some(values.iter().sum::<i32>() as f32),
+        objectives: if values.len() > 1 { Some(objectives) } else { None },
+    }
+}
+
+// Returns an evaluated population that consists of the given individuals
+pub fn population(individuals: Vec<Individual<Numbers, Numbers>>) -> Population<Numbers, Numbers> {
+    let mut population = Population::with_capacity(individuals.len());
+    population.individuals = individuals;
+    population.state = PopulationState::FitnessEvaluated;
+    population
+}
+
+// Returns the numbers of all individuals, in order
+pub fn values(population: &Population<Numbers, Numbers>) -> Vec<Vec<i32>> {
+    population.iter().map(|indiv| {
+        let numbers: &Numbers = indiv.genotype();
+        numbers.0.clone()
+    }).collect()
+}
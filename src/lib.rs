@@ -1,5 +1,4 @@
 use wasm_bindgen::prelude::*;
-use console_error_panic_hook;
 
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
 // allocator.
@@ -11,8 +10,18 @@ pub mod ca;
 pub mod ga;
 
 use std::any::Any;
+use std::convert::TryFrom;
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+use rayon::prelude::*;
 use std::fmt::{Debug, Display, Formatter, Result};
-use ca::{BitGrid, GameOfLife, GameOfLifeRunner, RunStats};
+use ca::{
+    BatchRunner, BitGrid, GameOfLife, GameOfLifeRunner, GridBorder, Immigration, Player, Rule,
+    RunStats
+};
+use rand::{Rng, RngCore, SeedableRng};
+use rand::seq::index;
+use rand_pcg::Pcg64Mcg;
+use serde::{Deserialize, Serialize};
 use ga::{
     EvolutionaryAlgorithm,
     Phenotype,
@@ -25,6 +34,13 @@ use ga::{
     Recombination,
     PopulationStats,
 };
+use ga::island::{
+    IslandModel,
+    IslandModelState,
+    MigrantSelection,
+    Topology,
+};
+use ga::steady_state::Replacement;
 use ga::binary::{
     BinaryChromosome,
     BinaryBitMutation,
@@ -35,35 +51,78 @@ use ga::selection::{
     TournamentSelection,
 };
 
-const SEED_PATCH_SIZE: usize = 8;
-const TOTAL_SEED_CELLS: usize = SEED_PATCH_SIZE * SEED_PATCH_SIZE;
-
+#[allow(dead_code)]
 #[derive(Debug)]
-struct MySimpleExpressor {}
+struct MySimpleExpressor {
+    seed_width: usize,
+    seed_height: usize,
+}
 
 #[derive(Debug)]
 struct MyNeutralExpressor {
+    seed_width: usize,
+    seed_height: usize,
     bits_per_cell: u8,
     num_groups: u8,
     group_values: Vec<bool>,
 }
 
-#[derive(Hash, Eq, PartialEq)]
+#[derive(Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct MyPhenotype {
     bit_grid: BitGrid,
 }
 
-trait FitnessCalculator {
-    fn calculate_fitness(&self, run_stats: &RunStats) -> f32;
+trait FitnessCalculator: Send + Sync {
+    fn calculate_fitness(&self, run_stats: &RunStats, final_state: &GameOfLife) -> f32;
+
+    fn num_objectives(&self) -> usize {
+        1
+    }
+
+    fn calculate_objectives(&self, run_stats: &RunStats, final_state: &GameOfLife) -> Vec<f32> {
+        vec![self.calculate_fitness(run_stats, final_state)]
+    }
+}
+
+// Evaluates a share of each batch of phenotypes. With the parallel feature, there is one worker
+// per thread, so that each thread runs its own gardens.
+struct BatchWorker {
+    gardens: Vec<GameOfLife>,
+    batch_runner: BatchRunner,
+    num_ca_steps: u64,
 }
 
 struct MyEvaluator {
     gol: GameOfLife,
+    // The position of the top-left cell of the seed in the garden
+    seed_x: usize,
+    seed_y: usize,
     gol_runner: GameOfLifeRunner,
-    num_ca_steps: u32,
+    border: GridBorder,
+    workers: Vec<BatchWorker>,
+    num_ca_steps: u64,
     fitness_calculator: Box<dyn FitnessCalculator>,
 }
 
+// Evaluates seeds by letting them battle in pairs in a two-colour CA. The fitness of a seed is
+// the share of the live cells that it owns at the end of a battle, averaged over all its battles.
+struct BattleEvaluator {
+    garden: Immigration,
+    // The position of the top-left cell of the seed of the first player. The seed of the second
+    // player is flipped horizontally and placed at the opposite end of the garden.
+    seed_x: usize,
+    seed_y: usize,
+    pairing: Pairing,
+    num_opponents: usize,
+    num_battle_steps: u32,
+    num_ca_steps: u64,
+    // Used to sample opponents
+    rng: Pcg64Mcg,
+    // With two populations, the seeds of the other population, which the seeds being evaluated
+    // battle instead of each other
+    opponents: Option<Vec<BitGrid>>,
+}
+
 #[derive(Debug)]
 struct MyConfig {
     genotype_length: usize,
@@ -72,52 +131,160 @@ struct MyConfig {
 }
 
 #[wasm_bindgen]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 pub struct FitnessWeights {
     pub num_toggled_cells: f32,
     pub num_toggled_steps: f32,
     pub max_alive_cells: f32,
     pub max_alive_steps: f32,
     pub num_start_cells: f32,
+    pub cycle_period: f32,
+    pub moving_object: f32,
+    pub distinct_objects: f32,
+    pub escaped_spaceships: f32,
 }
 
 struct WeightedFitness {
     fitness_weights: FitnessWeights,
 }
 
+// The properties of a run that can be optimised in multi-objective mode. These match the ones
+// that can be weighted in single-objective mode.
 #[wasm_bindgen]
-#[derive(Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Objective {
+    NumToggledCells,
+    NumToggledSteps,
+    MaxAliveCells,
+    MaxAliveSteps,
+    NumStartCells,
+    CyclePeriod,
+    MovingObject,
+    DistinctObjects,
+    EscapedSpaceships,
+}
+
+// Calculates a separate value for each objective. Objectives that should be minimised are negated
+struct MultiObjectiveFitness {
+    objectives: Vec<(Objective, bool)>,
+}
+
+// Where the seed is placed in the garden
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SeedAnchor {
+    Center,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    // At the offset specified in the settings
+    Offset,
+}
+
+// How opponents are chosen when seeds battle each other
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Pairing {
+    // Each seed battles all other seeds in the population
+    RoundRobin,
+    // Each seed battles a fixed number of randomly chosen seeds. It may battle more often, as it
+    // can also be chosen as opponent by others.
+    Sampled,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 // This struct contains the settings that can be modified by the user
 pub struct MyEaSettings {
     // Problem settings
-    garden_size: usize,
-    wrap_border: bool,
+    garden_width: usize,
+    garden_height: usize,
+    seed_width: usize,
+    seed_height: usize,
+    seed_anchor: SeedAnchor,
+    // Position of the top-left cell of the seed. Only used when the anchor is SeedAnchor::Offset
+    seed_offset_x: usize,
+    seed_offset_y: usize,
+    border: GridBorder,
+    rule: Rule,
 
     // Fitness weights
     fitness_weights: FitnessWeights,
 
+    // When set, the objectives are optimised using NSGA-II instead of combining them with the
+    // fitness weights. For each objective, it is specified whether it is maximised.
+    objectives: Vec<(Objective, bool)>,
+
+    // Co-evolution settings. When enabled, seeds battle each other in a two-colour CA instead of
+    // being evaluated using the fitness weights. The seed anchor is then ignored. With two
+    // populations, each evolves on its own island, without migration, and its seeds battle those
+    // of the other population of the previous generation.
+    coevolution: bool,
+    two_populations: bool,
+    pairing: Pairing,
+    num_opponents: usize,
+    num_battle_steps: u32,
+
     // Optimization settings
+    // The seed for all random choices. When not set, a random seed is used
+    seed: Option<u64>,
     mutation_rate: f32,
     recombination_rate: f32,
     population_size: usize,
     tournament_size: usize,
     elitism: bool,
+
+    // Steady-state settings. When enabled, each step creates only a few offspring, which replace
+    // existing individuals. Elitism is then ignored. Inverse tournaments use the tournament size
+    steady_state: bool,
+    num_offspring: usize,
+    replacement: Replacement,
+
+    // Island model settings. The population size applies to each island. Islands can have their
+    // own tournament size and elitism, which otherwise default to the settings above
+    num_islands: usize,
+    island_selections: Vec<(usize, bool)>,
+    topology: Topology,
+    migrant_selection: MigrantSelection,
+    num_migrants: usize,
+    migration_interval: u32,
 }
 
 #[wasm_bindgen]
 pub struct MyEvolutionaryAlgorithm {
-    ea: EvolutionaryAlgorithm<BinaryChromosome, MyPhenotype>,
+    ea: IslandModel<BinaryChromosome, MyPhenotype>,
+    settings: MyEaSettings,
 
     population_stats: Option<PopulationStats<BinaryChromosome, MyPhenotype>>,
+    island_stats: Vec<Option<PopulationStats<BinaryChromosome, MyPhenotype>>>,
 
     prev_num_evaluations: u32,
-    prev_num_ca_steps: u32,
+    prev_num_ca_steps: u64,
 
     gene_counts: Vec<u32>,
     gene_distribution: Vec<f32>,
 
     cell_counts: Vec<u32>,
     cell_distribution: Vec<f32>,
+
+    // The objectives of all individuals in the Pareto front, one after the other
+    pareto_front: Vec<f32>,
+}
+
+// Incremented whenever the format of checkpoints changes
+const CHECKPOINT_VERSION: u32 = 6;
+
+// Everything that is needed to resume a run of MyEvolutionaryAlgorithm. The evaluator state is
+// stored for each island.
+#[derive(Serialize, Deserialize)]
+struct Checkpoint {
+    version: u32,
+    settings: MyEaSettings,
+    ea_state: IslandModelState<BinaryChromosome, MyPhenotype>,
+    num_ca_steps: Vec<u64>,
+    // The generators that the battle evaluators use to sample opponents, if any
+    battle_rngs: Vec<Option<Pcg64Mcg>>,
 }
 
 impl Phenotype for MyPhenotype {}
@@ -129,14 +296,29 @@ impl Debug for MyPhenotype {
     }
 }
 
+#[allow(dead_code)]
+impl MySimpleExpressor {
+
+    fn new(seed_width: usize, seed_height: usize) -> Self {
+        MySimpleExpressor {
+            seed_width,
+            seed_height,
+        }
+    }
+
+    fn genotype_length(&self) -> usize {
+        self.seed_width * self.seed_height
+    }
+}
+
 impl Expressor<BinaryChromosome, MyPhenotype> for MySimpleExpressor {
 
     fn express(&mut self, genotype: &BinaryChromosome) -> MyPhenotype {
-        let mut bit_grid = BitGrid::new(SEED_PATCH_SIZE, SEED_PATCH_SIZE);
+        let mut bit_grid = BitGrid::new(self.seed_width, self.seed_height);
         let mut index = 0;
 
-        for x in 0..SEED_PATCH_SIZE {
-            for y in 0..SEED_PATCH_SIZE {
+        for x in 0..self.seed_width {
+            for y in 0..self.seed_height {
                 if genotype.bits[index] {
                     bit_grid.set(x, y);
                 }
@@ -152,11 +334,13 @@ impl Expressor<BinaryChromosome, MyPhenotype> for MySimpleExpressor {
 
 impl MyNeutralExpressor {
 
-    fn new(bits_per_cell: u8) -> Self {
+    fn new(seed_width: usize, seed_height: usize, bits_per_cell: u8) -> Self {
         assert!(bits_per_cell > 1, "Neutral encoding should have more than one bit per cell");
 
         let num_groups = 0x1 << (bits_per_cell - 1);
         MyNeutralExpressor {
+            seed_width,
+            seed_height,
             bits_per_cell,
             num_groups,
             group_values: Vec::with_capacity(num_groups as usize),
@@ -164,7 +348,7 @@ impl MyNeutralExpressor {
     }
 
     fn genotype_length(&self) -> usize {
-        let num_seed_cells = SEED_PATCH_SIZE * SEED_PATCH_SIZE;
+        let num_seed_cells = self.seed_width * self.seed_height;
 
         num_seed_cells * self.bits_per_cell as usize + 2 * self.num_groups as usize
     }
@@ -173,7 +357,7 @@ impl MyNeutralExpressor {
 impl Expressor<BinaryChromosome, MyPhenotype> for MyNeutralExpressor {
 
     fn express(&mut self, genotype: &BinaryChromosome) -> MyPhenotype {
-        let mut bit_grid = BitGrid::new(SEED_PATCH_SIZE, SEED_PATCH_SIZE);
+        let mut bit_grid = BitGrid::new(self.seed_width, self.seed_height);
 
         // Determine values of each group
         let ng = self.num_groups as usize;
@@ -189,17 +373,15 @@ impl Expressor<BinaryChromosome, MyPhenotype> for MyNeutralExpressor {
             if genotype.bits[ng + i] {
                 // Swap with next
                 let j = (i + 1) % ng;
-                let tmp = self.group_values[i];
-                self.group_values[i] = self.group_values[j];
-                self.group_values[j] = tmp;
+                self.group_values.swap(i, j);
             }
         }
 
         // Skip first (num_groups * 2) bits as these are used for storing value of each group
         let mut index = 2 * ng;
 
-        for y in 0..SEED_PATCH_SIZE {
-            for x in 0..SEED_PATCH_SIZE {
+        for y in 0..self.seed_height {
+            for x in 0..self.seed_width {
                 let mut n = (self.bits_per_cell - 1) as usize;
                 let cell_state = if genotype.bits[index] {
                     // Use local majority voting
@@ -218,7 +400,7 @@ impl Expressor<BinaryChromosome, MyPhenotype> for MyNeutralExpressor {
                     let mut group = 0;
 
                     while n > 0 {
-                        group = group << 1;
+                        group <<= 1;
                         if genotype.bits[index + n] {
                             group += 1;
                         }
@@ -242,22 +424,90 @@ impl Expressor<BinaryChromosome, MyPhenotype> for MyNeutralExpressor {
     }
 }
 
+impl BatchWorker {
+    fn new() -> Self {
+        BatchWorker {
+            gardens: Vec::new(),
+            batch_runner: BatchRunner::new(100, 2.0),
+            num_ca_steps: 0,
+        }
+    }
+
+    // Evaluates the phenotypes in gardens that are configured like the given one, with each seed
+    // placed at the given position
+    fn evaluate(
+        &mut self,
+        phenotypes: &[&MyPhenotype],
+        template: &GameOfLife,
+        border: GridBorder,
+        (seed_x, seed_y): (usize, usize),
+        fitness_calculator: &dyn FitnessCalculator,
+    ) -> Vec<Vec<f32>> {
+        while self.gardens.len() < phenotypes.len() {
+            let mut garden = GameOfLife::new_result(
+                template.width(), template.height(), border
+            ).unwrap();
+            garden.set_rule(*template.rule());
+            self.gardens.push(garden);
+        }
+
+        let gardens = &mut self.gardens[..phenotypes.len()];
+        for (garden, phenotype) in gardens.iter_mut().zip(phenotypes.iter()) {
+            garden.reset();
+            garden.paste(&phenotype.bit_grid, seed_x, seed_y).expect("Seed does not fit");
+        }
+
+        let all_stats = self.batch_runner.run(gardens);
+
+        let num_ca_steps = &mut self.num_ca_steps;
+        all_stats.iter().zip(gardens.iter()).map(|(stats, garden)| {
+            *num_ca_steps += stats.num_steps as u64;
+            fitness_calculator.calculate_objectives(stats, garden)
+        }).collect()
+    }
+}
+
 impl MyEvaluator {
     pub fn new(
-        garden_size: usize,
-        wrap_border: bool,
+        garden_width: usize,
+        garden_height: usize,
+        (seed_x, seed_y): (usize, usize),
+        border: GridBorder,
+        rule: Rule,
         fitness_calculator: Box<dyn FitnessCalculator>
     ) -> Self {
+        let mut gol = GameOfLife::new_result(garden_width, garden_height, border).unwrap();
+        gol.set_rule(rule);
+
         MyEvaluator {
-            gol: GameOfLife::new(garden_size, garden_size, wrap_border),
+            gol,
+            seed_x,
+            seed_y,
             gol_runner: GameOfLifeRunner::new(100, 2.0),
+            border,
+            workers: (0..Self::num_workers()).map(|_| BatchWorker::new()).collect(),
             num_ca_steps: 0,
             fitness_calculator,
         }
     }
 
-    pub fn num_ca_steps(&self) -> u32 {
-        self.num_ca_steps
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    fn num_workers() -> usize {
+        rayon::current_num_threads()
+    }
+
+    #[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+    fn num_workers() -> usize {
+        1
+    }
+
+    pub fn num_ca_steps(&self) -> u64 {
+        self.num_ca_steps + self.workers.iter().map(|worker| worker.num_ca_steps).sum::<u64>()
+    }
+
+    fn set_num_ca_steps(&mut self, num_ca_steps: u64) {
+        self.num_ca_steps = num_ca_steps;
+        self.workers.iter_mut().for_each(|worker| worker.num_ca_steps = 0);
     }
 }
 
@@ -272,43 +522,291 @@ impl Evaluator<MyPhenotype> for MyEvaluator {
 
     fn evaluate(&mut self, phenotype: &MyPhenotype) -> f32 {
         self.gol.reset();
+        self.gol.paste(&phenotype.bit_grid, self.seed_x, self.seed_y).expect("Seed does not fit");
 
-        let x0 = (self.gol.width() - SEED_PATCH_SIZE) / 2;
-        let y0 = (self.gol.height() - SEED_PATCH_SIZE) / 2;
-        for x in 0..SEED_PATCH_SIZE {
-            for y in 0..SEED_PATCH_SIZE {
-                if phenotype.bit_grid.get(x, y) {
-                    self.gol.set(x0 + x, y0 + y);
-                }
-            }
+        let stats = self.gol_runner.run(&mut self.gol);
+        self.num_ca_steps += stats.num_steps as u64;
+
+        self.fitness_calculator.calculate_fitness(&stats, &self.gol)
+    }
+
+    fn evaluate_batch(&mut self, phenotypes: &[&MyPhenotype]) -> Vec<f32> {
+        self.evaluate_objectives(phenotypes).into_iter().map(|objectives| objectives[0]).collect()
+    }
+
+    fn num_objectives(&self) -> usize {
+        self.fitness_calculator.num_objectives()
+    }
+
+    #[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+    fn evaluate_objectives(&mut self, phenotypes: &[&MyPhenotype]) -> Vec<Vec<f32>> {
+        self.workers[0].evaluate(
+            phenotypes, &self.gol, self.border, (self.seed_x, self.seed_y),
+            self.fitness_calculator.as_ref()
+        )
+    }
+
+    // Divides the phenotypes evenly over the workers, which each evaluate their share on their
+    // own thread
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    fn evaluate_objectives(&mut self, phenotypes: &[&MyPhenotype]) -> Vec<Vec<f32>> {
+        if phenotypes.is_empty() {
+            return Vec::new();
         }
 
-        let stats = self.gol_runner.run(&mut self.gol);
-        self.num_ca_steps += stats.num_steps;
+        let chunk_size = phenotypes.len().div_ceil(self.workers.len());
+        let (gol, border) = (&self.gol, self.border);
+        let seed_position = (self.seed_x, self.seed_y);
+        let fitness_calculator = self.fitness_calculator.as_ref();
 
-        self.fitness_calculator.calculate_fitness(&stats)
+        self.workers.par_iter_mut().zip(phenotypes.par_chunks(chunk_size)).map(
+            |(worker, chunk)| worker.evaluate(chunk, gol, border, seed_position, fitness_calculator)
+        ).collect::<Vec<Vec<Vec<f32>>>>().concat()
     }
 
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// Returns the number of distinct objects in the state. The census only recognises the objects of
+// Conway's rule, so none are counted for other rules.
+fn num_distinct_objects(final_state: &GameOfLife) -> u32 {
+    if final_state.rule().is_conway() {
+        final_state.census().num_distinct()
+    } else {
+        0
+    }
 }
 
 impl WeightedFitness {
     fn new(fitness_weights: FitnessWeights) -> Self {
         WeightedFitness {
-            fitness_weights: fitness_weights
+            fitness_weights,
+        }
+    }
+
+    fn distinct_objects(&self, final_state: &GameOfLife) -> f32 {
+        // Only take the census when it is needed, as it is relatively expensive
+        if self.fitness_weights.distinct_objects == 0.0 {
+            return 0.0;
         }
+
+        (num_distinct_objects(final_state) as f32) * self.fitness_weights.distinct_objects
     }
 }
 
 impl FitnessCalculator for WeightedFitness {
-    fn calculate_fitness(&self, stats: &RunStats) -> f32 {
+    fn calculate_fitness(&self, stats: &RunStats, final_state: &GameOfLife) -> f32 {
         (stats.num_toggled as f32) * self.fitness_weights.num_toggled_cells
         + (stats.num_toggled_steps as f32) * self.fitness_weights.num_toggled_steps
         + (stats.max_cells as f32) * self.fitness_weights.max_alive_cells
         + (stats.max_cells_steps as f32) * self.fitness_weights.max_alive_steps
         + (stats.ini_cells as f32) * self.fitness_weights.num_start_cells
+        + (stats.period as f32) * self.fitness_weights.cycle_period
+        + if stats.translation_period > 0 { self.fitness_weights.moving_object } else { 0.0 }
+        + (stats.num_escaped() as f32) * self.fitness_weights.escaped_spaceships
+        + self.distinct_objects(final_state)
+    }
+}
+
+impl BattleEvaluator {
+    pub fn new(settings: &MyEaSettings, seed: u64) -> std::result::Result<Self, String> {
+        let (seed_x, seed_y) = settings.battle_seed_position();
+
+        Ok(BattleEvaluator {
+            garden: Immigration::new_result(
+                settings.garden_width, settings.garden_height, settings.border, settings.rule
+            )?,
+            seed_x,
+            seed_y,
+            pairing: settings.pairing,
+            num_opponents: settings.num_opponents,
+            num_battle_steps: settings.num_battle_steps,
+            num_ca_steps: 0,
+            rng: Pcg64Mcg::seed_from_u64(seed),
+            // There are no opponents until the other population has been evaluated
+            opponents: if settings.two_populations { Some(Vec::new()) } else { None },
+        })
+    }
+
+    pub fn num_ca_steps(&self) -> u64 {
+        self.num_ca_steps
+    }
+
+    fn set_num_ca_steps(&mut self, num_ca_steps: u64) {
+        self.num_ca_steps = num_ca_steps;
+    }
+
+    // Returns the share of the live cells owned by the first player at the end of the battle.
+    // When all cells died, the battle is a draw.
+    fn battle(&mut self, first: &BitGrid, second: &BitGrid) -> f32 {
+        let width = self.garden.width();
+
+        self.garden.reset();
+        self.garden.place_result(first, self.seed_x, self.seed_y, Player::First)
+            .expect("Seed does not fit");
+        for y in 0..second.height() {
+            for x in 0..second.width() {
+                if second.get(x, y) {
+                    self.garden.set(width - 1 - self.seed_x - x, self.seed_y + y, Player::Second);
+                }
+            }
+        }
+
+        for _ in 0..self.num_battle_steps {
+            self.garden.step();
+        }
+        self.num_ca_steps += self.num_battle_steps as u64;
+
+        let num_first = self.garden.num_cells(Player::First);
+        let num_second = self.garden.num_cells(Player::Second);
+        if num_first + num_second == 0 {
+            0.5
+        } else {
+            num_first as f32 / (num_first + num_second) as f32
+        }
+    }
+
+    // Returns the pairs of indices of the seeds that should battle each other. The first seed of
+    // each pair plays as the first player. As ties go to the first player, the roles alternate,
+    // so that each seed plays about half of its battles as the first player.
+    fn pairs(&mut self, num_seeds: usize) -> Vec<(usize, usize)> {
+        let ordered = |i: usize, j: usize| if (i + j).is_multiple_of(2) { (i, j) } else { (j, i) };
+
+        match self.pairing {
+            Pairing::RoundRobin => (0..num_seeds).flat_map(
+                |i| (i + 1..num_seeds).map(move |j| ordered(i, j))
+            ).collect(),
+            Pairing::Sampled => {
+                let num_opponents = self.num_opponents.min(num_seeds.saturating_sub(1));
+                let rng = &mut self.rng;
+                (0..num_seeds).flat_map(|i| {
+                    // Sample from all other seeds by skipping over the seed itself
+                    index::sample(rng, num_seeds - 1, num_opponents).into_iter().map(
+                        move |j| ordered(i, if j < i { j } else { j + 1 })
+                    ).collect::<Vec<_>>()
+                }).collect()
+            },
+        }
+    }
+
+    // Returns the average share of the seed in its battles against the seeds of the other
+    // population. Like within a population, the roles alternate.
+    fn battle_opponents(&mut self, index: usize, seed: &BitGrid, opponents: &[BitGrid]) -> f32 {
+        let selected = match self.pairing {
+            Pairing::RoundRobin => (0..opponents.len()).collect(),
+            Pairing::Sampled => {
+                let num_opponents = self.num_opponents.min(opponents.len());
+                index::sample(&mut self.rng, opponents.len(), num_opponents).into_vec()
+            },
+        };
+        if selected.is_empty() {
+            return 0.5;
+        }
+
+        let total_share: f32 = selected.iter().map(|&k| if (index + k).is_multiple_of(2) {
+            self.battle(seed, &opponents[k])
+        } else {
+            1.0 - self.battle(&opponents[k], seed)
+        }).sum();
+
+        total_share / selected.len() as f32
+    }
+}
+
+impl Debug for BattleEvaluator {
+    // Only show class name
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("BattleEvaluator").finish()
+    }
+}
+
+impl Evaluator<MyPhenotype> for BattleEvaluator {
+
+    // Without any opponents, a seed is rated as if all its battles were a draw
+    fn evaluate(&mut self, phenotype: &MyPhenotype) -> f32 {
+        self.bulk_evaluate(&[phenotype])[0]
+    }
+
+    fn is_interaction_based(&self) -> bool {
+        true
+    }
+
+    fn bulk_evaluate(&mut self, phenotypes: &[&MyPhenotype]) -> Vec<f32> {
+        if let Some(opponents) = self.opponents.take() {
+            let fitness = phenotypes.iter().enumerate().map(
+                |(i, phenotype)| self.battle_opponents(i, &phenotype.bit_grid, &opponents)
+            ).collect();
+            self.opponents = Some(opponents);
+            return fitness;
+        }
+
+        let mut total_share = vec![0.0; phenotypes.len()];
+        let mut num_battles = vec![0; phenotypes.len()];
+
+        for (i, j) in self.pairs(phenotypes.len()) {
+            let share = self.battle(&phenotypes[i].bit_grid, &phenotypes[j].bit_grid);
+            total_share[i] += share;
+            total_share[j] += 1.0 - share;
+            num_battles[i] += 1;
+            num_battles[j] += 1;
+        }
+
+        total_share.iter().zip(num_battles.iter()).map(|(&share, &num)| {
+            if num > 0 { share / num as f32 } else { 0.5 }
+        }).collect()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl MultiObjectiveFitness {
+    fn new(objectives: Vec<(Objective, bool)>) -> Self {
+        MultiObjectiveFitness {
+            objectives,
+        }
+    }
+
+    fn value(&self, objective: Objective, stats: &RunStats, final_state: &GameOfLife) -> f32 {
+        match objective {
+            Objective::NumToggledCells => stats.num_toggled as f32,
+            Objective::NumToggledSteps => stats.num_toggled_steps as f32,
+            Objective::MaxAliveCells => stats.max_cells as f32,
+            Objective::MaxAliveSteps => stats.max_cells_steps as f32,
+            Objective::NumStartCells => stats.ini_cells as f32,
+            Objective::CyclePeriod => stats.period as f32,
+            Objective::MovingObject => if stats.translation_period > 0 { 1.0 } else { 0.0 },
+            Objective::DistinctObjects => num_distinct_objects(final_state) as f32,
+            Objective::EscapedSpaceships => stats.num_escaped() as f32,
+        }
+    }
+}
+
+impl FitnessCalculator for MultiObjectiveFitness {
+    fn calculate_fitness(&self, stats: &RunStats, final_state: &GameOfLife) -> f32 {
+        self.calculate_objectives(stats, final_state)[0]
+    }
+
+    fn num_objectives(&self) -> usize {
+        self.objectives.len()
+    }
+
+    fn calculate_objectives(&self, stats: &RunStats, final_state: &GameOfLife) -> Vec<f32> {
+        self.objectives.iter().map(|&(objective, maximize)| {
+            let value = self.value(objective, stats, final_state);
+            if maximize { value } else { -value }
+        }).collect()
     }
 }
 
@@ -323,39 +821,75 @@ impl MyConfig {
 }
 
 impl GenotypeFactory<BinaryChromosome> for MyConfig {
-    fn create(&self) -> BinaryChromosome {
-        BinaryChromosome::new(self.genotype_length)
+    fn create(&self, rng: &mut dyn RngCore) -> BinaryChromosome {
+        BinaryChromosome::new(self.genotype_length, rng)
     }
 }
 
 impl GenotypeManipulation<BinaryChromosome> for MyConfig {
-    fn mutate(&self, target: &mut BinaryChromosome) {
-        self.mutation.mutate(target);
+    fn mutate(&self, target: &mut BinaryChromosome, rng: &mut dyn RngCore) {
+        self.mutation.mutate(target, rng);
     }
 
-    fn recombine(&self, parent1: &BinaryChromosome, parent2: &BinaryChromosome) -> BinaryChromosome {
-        self.recombination.recombine(parent1, parent2)
+    fn recombine(
+        &self, parent1: &BinaryChromosome, parent2: &BinaryChromosome, rng: &mut dyn RngCore
+    ) -> BinaryChromosome {
+        self.recombination.recombine(parent1, parent2, rng)
     }
 }
 
 impl GenotypeConfig<BinaryChromosome> for MyConfig {}
 
-pub fn setup_ga(settings: &MyEaSettings) -> EvolutionaryAlgorithm<BinaryChromosome, MyPhenotype> {
-    let expressor = MyNeutralExpressor::new(4);
-    let main_selector = Box::new(TournamentSelection::new(
-        settings.tournament_size
-    ));
+// Returns the number of CA steps that the evaluator has executed so far
+fn evaluator_num_ca_steps(evaluator: &dyn Evaluator<MyPhenotype>) -> u64 {
+    let evaluator = evaluator.as_any();
+    if let Some(my_evaluator) = evaluator.downcast_ref::<MyEvaluator>() {
+        my_evaluator.num_ca_steps()
+    } else if let Some(battle_evaluator) = evaluator.downcast_ref::<BattleEvaluator>() {
+        battle_evaluator.num_ca_steps()
+    } else {
+        panic!("Expected MyEvaluator or BattleEvaluator as evaluator")
+    }
+}
+
+// Sets up the EA of a single island. Assumes that the settings are valid.
+fn setup_island(
+    settings: &MyEaSettings, island: usize, seed: u64
+) -> std::result::Result<EvolutionaryAlgorithm<BinaryChromosome, MyPhenotype>, String> {
+    let (tournament_size, elitism) = settings.island_selections.get(island).copied().unwrap_or(
+        (settings.tournament_size, settings.elitism)
+    );
+    let expressor = MyNeutralExpressor::new(settings.seed_width, settings.seed_height, 4);
+    let main_selector = Box::new(TournamentSelection::new(tournament_size));
+
+    let evaluator: Box<dyn Evaluator<MyPhenotype>> = if settings.coevolution {
+        // Derive a different seed, so that the evaluator's choices do not mirror those of the EA
+        Box::new(BattleEvaluator::new(settings, seed.wrapping_add(1))?)
+    } else {
+        let fitness_calculator: Box<dyn FitnessCalculator> = if settings.objectives.is_empty() {
+            Box::new(WeightedFitness::new(settings.fitness_weights))
+        } else {
+            Box::new(MultiObjectiveFitness::new(settings.objectives.clone()))
+        };
 
-    EvolutionaryAlgorithm::new(
+        Box::new(MyEvaluator::new(
+            settings.garden_width,
+            settings.garden_height,
+            settings.seed_position(),
+            settings.border,
+            settings.rule,
+            fitness_calculator
+        ))
+    };
+
+    let ea = EvolutionaryAlgorithm::new(
         settings.population_size,
         Box::new(MyConfig::new(expressor.genotype_length())),
         Box::new(expressor),
-        Box::new(MyEvaluator::new(
-            settings.garden_size,
-            settings.wrap_border,
-            Box::new(WeightedFitness::new(settings.fitness_weights))
-        )),
-        if settings.elitism {
+        evaluator,
+        // Steady-state replacement keeps the best individuals, and elitism would make the best
+        // individual a parent every step
+        if elitism && !settings.steady_state {
             Box::new(ElitismSelection::new(1, main_selector))
         } else {
             main_selector
@@ -364,13 +898,171 @@ pub fn setup_ga(settings: &MyEaSettings) -> EvolutionaryAlgorithm<BinaryChromoso
         settings.mutation_rate
     ).set_recombination_prob(
         settings.recombination_rate
-    ).enable_fitness_cache()
+    ).set_seed(
+        seed
+    ).enable_fitness_cache();
+
+    // NSGA-II replaces the tournament and elitism settings by its own selection
+    Ok(if !settings.objectives.is_empty() {
+        ea.enable_nsga2()
+    } else if settings.steady_state {
+        ea.enable_steady_state(settings.num_offspring, settings.replacement, tournament_size)
+    } else {
+        ea
+    })
+}
+
+pub fn setup_ga(
+    settings: &MyEaSettings
+) -> std::result::Result<IslandModel<BinaryChromosome, MyPhenotype>, String> {
+    settings.validate_result()?;
+
+    let seed = settings.seed.unwrap_or_else(|| rand::thread_rng().gen());
+    // Each island uses two seeds, one for its EA and one for its evaluator. This way, the first
+    // island runs exactly as a run without islands.
+    let islands = (0..settings.num_islands).map(
+        |island| setup_island(settings, island, seed.wrapping_add(2 * island as u64))
+    ).collect::<std::result::Result<Vec<_>, String>>()?;
+
+    Ok(IslandModel::new(islands)
+        .set_topology(settings.topology)
+        .set_migrant_selection(settings.migrant_selection)
+        // The two populations evolve separately, they only meet in battle
+        .set_num_migrants(if settings.two_populations { 0 } else { settings.num_migrants })
+        .set_migration_interval(settings.migration_interval)
+        .set_seed(seed.wrapping_sub(1)))
 }
 
 impl MyEvolutionaryAlgorithm {
-    pub fn ea(&self) -> &EvolutionaryAlgorithm<BinaryChromosome, MyPhenotype> {
+    fn with_ea(
+        ea: IslandModel<BinaryChromosome, MyPhenotype>, settings: &MyEaSettings
+    ) -> Self {
+        MyEvolutionaryAlgorithm {
+            ea,
+            settings: settings.clone(),
+            population_stats: None,
+            island_stats: vec![],
+            prev_num_evaluations: 0,
+            prev_num_ca_steps: 0,
+            gene_counts: vec![],
+            gene_distribution: vec![],
+            cell_counts: vec![],
+            cell_distribution: vec![],
+            pareto_front: vec![],
+        }
+    }
+
+    // Returns the number of CA steps that the evaluators of all islands have executed so far
+    fn total_ca_steps(&self) -> u64 {
+        self.ea.islands().iter().map(|island| evaluator_num_ca_steps(island.evaluator())).sum()
+    }
+
+    pub fn new_result(settings: &MyEaSettings) -> std::result::Result<Self, String> {
+        Ok(MyEvolutionaryAlgorithm::with_ea(setup_ga(settings)?, settings))
+    }
+
+    // Starts a new run with the given settings. When these are invalid, the current run is kept.
+    pub fn reset_result(&mut self, settings: &MyEaSettings) -> std::result::Result<(), String> {
+        self.ea = setup_ga(settings)?;
+        self.settings = settings.clone();
+        self.population_stats = None;
+        self.island_stats.clear();
+        self.prev_num_evaluations = 0;
+        self.prev_num_ca_steps = 0;
+
+        Ok(())
+    }
+
+    pub fn ea(&self) -> &IslandModel<BinaryChromosome, MyPhenotype> {
         &self.ea
     }
+
+    // With two populations, lets the seeds of each island battle those of the other island in
+    // the next generation
+    fn exchange_opponents(&mut self) {
+        if !self.settings.two_populations {
+            return;
+        }
+
+        let seeds: Vec<Vec<BitGrid>> = self.ea.islands().iter().map(
+            |island| island.population().iter().filter_map(
+                |indiv| indiv.phenotype().map(|phenotype| phenotype.bit_grid.clone())
+            ).collect()
+        ).collect();
+        let num_islands = seeds.len();
+        for (i, island) in self.ea.islands_mut().iter_mut().enumerate() {
+            let evaluator = island.evaluator_mut().as_any_mut();
+            if let Some(battle_evaluator) = evaluator.downcast_mut::<BattleEvaluator>() {
+                battle_evaluator.opponents = Some(seeds[(i + 1) % num_islands].clone());
+            }
+        }
+    }
+
+    fn update_stats(&mut self) {
+        self.population_stats = self.ea.get_population_stats();
+        self.island_stats = (0..self.ea.num_islands()).map(
+            |island| self.ea.get_island_stats(island)
+        ).collect();
+    }
+
+    // Returns a checkpoint of the run in a compact binary format. The run can be resumed from it
+    // by loading the checkpoint with load_result.
+    pub fn save_result(&self) -> std::result::Result<Vec<u8>, String> {
+        let islands = self.ea.islands();
+
+        bincode::serialize(&Checkpoint {
+            version: CHECKPOINT_VERSION,
+            settings: self.settings.clone(),
+            ea_state: self.ea.state(),
+            num_ca_steps: islands.iter().map(
+                |island| evaluator_num_ca_steps(island.evaluator())
+            ).collect(),
+            battle_rngs: islands.iter().map(
+                |island| island.evaluator().as_any().downcast_ref::<BattleEvaluator>().map(
+                    |battle_evaluator| battle_evaluator.rng.clone()
+                )
+            ).collect(),
+        }).map_err(|err| format!("Failed to save checkpoint: {}", err))
+    }
+
+    // Restores a run from a checkpoint as returned by save_result. The run continues exactly where
+    // it was when the checkpoint was made.
+    pub fn load_result(bytes: &[u8]) -> std::result::Result<Self, String> {
+        let checkpoint: Checkpoint = bincode::deserialize(bytes)
+            .map_err(|err| format!("Invalid checkpoint: {}", err))?;
+        if checkpoint.version != CHECKPOINT_VERSION {
+            return Err(format!("Unsupported checkpoint version {}", checkpoint.version));
+        }
+
+        let mut ea = setup_ga(&checkpoint.settings)?;
+        ea.restore(checkpoint.ea_state)?;
+        if checkpoint.num_ca_steps.len() != ea.num_islands()
+            || checkpoint.battle_rngs.len() != ea.num_islands()
+        {
+            return Err("Checkpoint lacks evaluator state of some islands".to_string());
+        }
+
+        let evaluator_states = checkpoint.num_ca_steps.into_iter().zip(checkpoint.battle_rngs);
+        for (island, (num_ca_steps, battle_rng)) in ea.islands_mut().iter_mut().zip(
+            evaluator_states
+        ) {
+            let evaluator = island.evaluator_mut().as_any_mut();
+            if let Some(my_evaluator) = evaluator.downcast_mut::<MyEvaluator>() {
+                my_evaluator.set_num_ca_steps(num_ca_steps);
+            } else if let Some(battle_evaluator) = evaluator.downcast_mut::<BattleEvaluator>() {
+                battle_evaluator.set_num_ca_steps(num_ca_steps);
+                battle_evaluator.rng = battle_rng.ok_or("Checkpoint lacks battle state")?;
+            }
+        }
+
+        let mut my_ea = MyEvolutionaryAlgorithm::with_ea(ea, &checkpoint.settings);
+        my_ea.prev_num_evaluations = my_ea.num_evaluations();
+        my_ea.prev_num_ca_steps = my_ea.total_ca_steps();
+        my_ea.exchange_opponents();
+        my_ea.update_stats();
+
+        Ok(my_ea)
+    }
 }
 
 #[wasm_bindgen]
@@ -382,40 +1074,251 @@ impl FitnessWeights {
             max_alive_cells: 0.0,
             max_alive_steps: 0.0,
             num_start_cells: 0.0,
+            cycle_period: 0.0,
+            moving_object: 0.0,
+            distinct_objects: 0.0,
+            escaped_spaceships: 0.0,
         }
     }
 }
 
+impl Default for FitnessWeights {
+    fn default() -> Self {
+        FitnessWeights::new()
+    }
+}
+
+impl MyEaSettings {
+    pub fn validate_result(&self) -> std::result::Result<(), String> {
+        if self.seed_width == 0 || self.seed_height == 0 {
+            return Err("Seed should contain at least one cell".to_string());
+        }
+        GameOfLife::new_result(self.garden_width, self.garden_height, self.border)?;
+        if self.garden_width < self.seed_width || self.garden_height < self.seed_height {
+            return Err(format!(
+                "Garden of {}x{} cells is smaller than the seed of {}x{} cells",
+                self.garden_width, self.garden_height, self.seed_width, self.seed_height
+            ));
+        }
+        if self.seed_anchor == SeedAnchor::Offset && (
+            self.seed_offset_x + self.seed_width > self.garden_width ||
+            self.seed_offset_y + self.seed_height > self.garden_height
+        ) {
+            return Err(format!(
+                "Seed at offset ({}, {}) does not fit inside the garden",
+                self.seed_offset_x, self.seed_offset_y
+            ));
+        }
+        if self.objectives.len() == 1 {
+            return Err("Multi-objective optimisation requires at least two objectives".to_string());
+        }
+        if self.coevolution && !self.objectives.is_empty() {
+            return Err("Co-evolution does not support multiple objectives".to_string());
+        }
+        if self.steady_state && !self.objectives.is_empty() {
+            return Err("Steady-state evolution does not support multiple objectives".to_string());
+        }
+        if self.steady_state && self.coevolution {
+            return Err("Steady-state evolution does not support co-evolution".to_string());
+        }
+        if self.steady_state && self.num_offspring == 0 {
+            return Err("Steady-state evolution requires at least one offspring".to_string());
+        }
+        if self.num_islands == 0 {
+            return Err("There should be at least one island".to_string());
+        }
+        if self.island_selections.len() > self.num_islands {
+            return Err(format!(
+                "Selection settings are given for {} islands, but there are only {}",
+                self.island_selections.len(), self.num_islands
+            ));
+        }
+        if self.two_populations && (!self.coevolution || self.num_islands != 2) {
+            return Err("Two populations require co-evolution on two islands".to_string());
+        }
+        if self.num_islands > 1 && !self.two_populations {
+            if self.migration_interval == 0 {
+                return Err("Migration interval should be at least one generation".to_string());
+            }
+            // With a random topology, all other islands may pick the same destination
+            let num_arrivals = match self.topology {
+                Topology::FullyConnected | Topology::Random => {
+                    self.num_migrants * (self.num_islands - 1)
+                },
+                Topology::Ring => self.num_migrants,
+            };
+            if num_arrivals >= self.population_size {
+                return Err(format!(
+                    "Migrants would replace {} of the {} individuals on an island",
+                    num_arrivals, self.population_size
+                ));
+            }
+        }
+        if self.coevolution {
+            if self.garden_width < self.seed_width * 2 {
+                return Err(format!(
+                    "Garden of {} cells wide cannot hold two seeds of {} cells wide",
+                    self.garden_width, self.seed_width
+                ));
+            }
+            if self.pairing == Pairing::Sampled && self.num_opponents == 0 {
+                return Err("Each seed should battle at least one opponent".to_string());
+            }
+            Immigration::new_result(self.garden_width, self.garden_height, self.border, self.rule)?;
+        }
+
+        Ok(())
+    }
+
+    // Returns the position of the top-left cell of the seed of the first player in a battle. It
+    // is centered in the left half of the garden. Assumes that the settings are valid.
+    fn battle_seed_position(&self) -> (usize, usize) {
+        (
+            (self.garden_width / 2 - self.seed_width) / 2,
+            (self.garden_height - self.seed_height) / 2
+        )
+    }
+
+    // Returns the position of the top-left cell of the seed. Assumes that the settings are valid.
+    fn seed_position(&self) -> (usize, usize) {
+        let max_x = self.garden_width - self.seed_width;
+        let max_y = self.garden_height - self.seed_height;
+
+        match self.seed_anchor {
+            SeedAnchor::Center => (max_x / 2, max_y / 2),
+            SeedAnchor::TopLeft => (0, 0),
+            SeedAnchor::TopRight => (max_x, 0),
+            SeedAnchor::BottomLeft => (0, max_y),
+            SeedAnchor::BottomRight => (max_x, max_y),
+            SeedAnchor::Offset => (self.seed_offset_x, self.seed_offset_y),
+        }
+    }
+}
+
+impl Default for MyEaSettings {
+    fn default() -> Self {
+        MyEaSettings::new()
+    }
+}
+
 #[wasm_bindgen]
 impl MyEaSettings {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
         MyEaSettings {
-            garden_size: 64,
-            wrap_border: false,
+            garden_width: 64,
+            garden_height: 64,
+            seed_width: 8,
+            seed_height: 8,
+            seed_anchor: SeedAnchor::Center,
+            seed_offset_x: 0,
+            seed_offset_y: 0,
+            border: GridBorder::Zeroes,
+            rule: Rule::conway(),
             fitness_weights: FitnessWeights::new(),
+            objectives: Vec::new(),
+            coevolution: false,
+            two_populations: false,
+            pairing: Pairing::RoundRobin,
+            num_opponents: 5,
+            num_battle_steps: 200,
+            seed: None,
             mutation_rate: 0.9,
             recombination_rate: 0.4,
             population_size: 100,
             tournament_size: 2,
             elitism: true,
+            steady_state: false,
+            num_offspring: 2,
+            replacement: Replacement::Worst,
+            num_islands: 1,
+            island_selections: Vec::new(),
+            topology: Topology::Ring,
+            migrant_selection: MigrantSelection::Best,
+            num_migrants: 1,
+            migration_interval: 10,
         }
     }
 
+    // Sets both the width and height of the garden
     pub fn set_garden_size(mut self, garden_size: usize) -> Self {
-        self.garden_size = garden_size;
+        self.garden_width = garden_size;
+        self.garden_height = garden_size;
+        self
+    }
+
+    pub fn set_garden_width(mut self, garden_width: usize) -> Self {
+        self.garden_width = garden_width;
         self
     }
-    pub fn garden_size(&self) -> usize {
-        self.garden_size
+    pub fn garden_width(&self) -> usize {
+        self.garden_width
     }
 
-    pub fn set_border(mut self, wrap_border: bool) -> Self {
-        self.wrap_border = wrap_border;
+    pub fn set_garden_height(mut self, garden_height: usize) -> Self {
+        self.garden_height = garden_height;
         self
     }
-    pub fn border_wraps(&self) -> bool {
-        self.wrap_border
+    pub fn garden_height(&self) -> usize {
+        self.garden_height
+    }
+
+    pub fn set_seed_width(mut self, seed_width: usize) -> Self {
+        self.seed_width = seed_width;
+        self
+    }
+    pub fn seed_width(&self) -> usize {
+        self.seed_width
+    }
+
+    pub fn set_seed_height(mut self, seed_height: usize) -> Self {
+        self.seed_height = seed_height;
+        self
+    }
+    pub fn seed_height(&self) -> usize {
+        self.seed_height
+    }
+
+    pub fn set_seed_anchor(mut self, seed_anchor: SeedAnchor) -> Self {
+        self.seed_anchor = seed_anchor;
+        self
+    }
+    pub fn seed_anchor(&self) -> SeedAnchor {
+        self.seed_anchor
+    }
+
+    // Places the top-left cell of the seed at the given position. This also sets the seed anchor
+    pub fn set_seed_offset(mut self, x: usize, y: usize) -> Self {
+        self.seed_anchor = SeedAnchor::Offset;
+        self.seed_offset_x = x;
+        self.seed_offset_y = y;
+        self
+    }
+    pub fn seed_offset_x(&self) -> usize {
+        self.seed_offset_x
+    }
+    pub fn seed_offset_y(&self) -> usize {
+        self.seed_offset_y
+    }
+
+    pub fn validate(&self) -> std::result::Result<(), JsValue> {
+        self.validate_result().map_err(|msg| JsValue::from_str(&msg))
+    }
+
+    pub fn set_border(mut self, border: GridBorder) -> Self {
+        self.border = border;
+        self
+    }
+    pub fn border(&self) -> GridBorder {
+        self.border
+    }
+
+    pub fn set_rule(mut self, rulestring: &str) -> std::result::Result<MyEaSettings, JsValue> {
+        self.rule = rulestring.parse().map_err(|msg: String| JsValue::from_str(&msg))?;
+        Ok(self)
+    }
+    pub fn rule(&self) -> String {
+        format!("{}", self.rule)
     }
 
     pub fn set_fw_num_toggled_cells(mut self, value: f32) -> Self {
@@ -438,6 +1341,84 @@ impl MyEaSettings {
         self.fitness_weights.num_start_cells = value;
         self
     }
+    pub fn set_fw_cycle_period(mut self, value: f32) -> Self {
+        self.fitness_weights.cycle_period = value;
+        self
+    }
+    pub fn set_fw_moving_object(mut self, value: f32) -> Self {
+        self.fitness_weights.moving_object = value;
+        self
+    }
+    pub fn set_fw_distinct_objects(mut self, value: f32) -> Self {
+        self.fitness_weights.distinct_objects = value;
+        self
+    }
+    pub fn set_fw_escaped_spaceships(mut self, value: f32) -> Self {
+        self.fitness_weights.escaped_spaceships = value;
+        self
+    }
+
+    // Adds an objective, which enables multi-objective optimisation
+    pub fn add_objective(mut self, objective: Objective, maximize: bool) -> Self {
+        self.objectives.push((objective, maximize));
+        self
+    }
+    pub fn clear_objectives(mut self) -> Self {
+        self.objectives.clear();
+        self
+    }
+    pub fn num_objectives(&self) -> usize {
+        self.objectives.len()
+    }
+
+    pub fn set_coevolution(mut self, coevolution: bool) -> Self {
+        self.coevolution = coevolution;
+        self
+    }
+    pub fn coevolution(&self) -> bool {
+        self.coevolution
+    }
+
+    pub fn set_pairing(mut self, pairing: Pairing) -> Self {
+        self.pairing = pairing;
+        self
+    }
+    pub fn pairing(&self) -> Pairing {
+        self.pairing
+    }
+
+    // The number of opponents that each seed battles when using Pairing::Sampled
+    pub fn set_num_opponents(mut self, num_opponents: usize) -> Self {
+        self.num_opponents = num_opponents;
+        self
+    }
+    pub fn num_opponents(&self) -> usize {
+        self.num_opponents
+    }
+
+    pub fn set_two_populations(mut self, two_populations: bool) -> Self {
+        self.two_populations = two_populations;
+        self
+    }
+    pub fn two_populations(&self) -> bool {
+        self.two_populations
+    }
+
+    pub fn set_num_battle_steps(mut self, num_battle_steps: u32) -> Self {
+        self.num_battle_steps = num_battle_steps;
+        self
+    }
+    pub fn num_battle_steps(&self) -> u32 {
+        self.num_battle_steps
+    }
+
+    pub fn set_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
 
     pub fn set_mutation_rate(mut self, mutation_rate: f32) -> Self {
         self.mutation_rate = mutation_rate;
@@ -478,41 +1459,135 @@ impl MyEaSettings {
     pub fn elitism(&self) -> bool {
         self.elitism
     }
+
+    pub fn set_steady_state(mut self, steady_state: bool) -> Self {
+        self.steady_state = steady_state;
+        self
+    }
+    pub fn steady_state(&self) -> bool {
+        self.steady_state
+    }
+
+    // The number of offspring that each steady-state step creates
+    pub fn set_num_offspring(mut self, num_offspring: usize) -> Self {
+        self.num_offspring = num_offspring;
+        self
+    }
+    pub fn num_offspring(&self) -> usize {
+        self.num_offspring
+    }
+
+    pub fn set_replacement(mut self, replacement: Replacement) -> Self {
+        self.replacement = replacement;
+        self
+    }
+    pub fn replacement(&self) -> Replacement {
+        self.replacement
+    }
+
+    pub fn set_num_islands(mut self, num_islands: usize) -> Self {
+        self.num_islands = num_islands;
+        self
+    }
+    pub fn num_islands(&self) -> usize {
+        self.num_islands
+    }
+
+    // Sets the selection of the next island that does not have its own selection settings yet
+    pub fn add_island_selection(mut self, tournament_size: usize, elitism: bool) -> Self {
+        self.island_selections.push((tournament_size, elitism));
+        self
+    }
+    pub fn clear_island_selections(mut self) -> Self {
+        self.island_selections.clear();
+        self
+    }
+
+    pub fn set_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn set_migrant_selection(mut self, migrant_selection: MigrantSelection) -> Self {
+        self.migrant_selection = migrant_selection;
+        self
+    }
+    pub fn migrant_selection(&self) -> MigrantSelection {
+        self.migrant_selection
+    }
+
+    // The number of individuals that each island sends to each of its destinations
+    pub fn set_num_migrants(mut self, num_migrants: usize) -> Self {
+        self.num_migrants = num_migrants;
+        self
+    }
+    pub fn num_migrants(&self) -> usize {
+        self.num_migrants
+    }
+
+    // The number of generations between migrations
+    pub fn set_migration_interval(mut self, migration_interval: u32) -> Self {
+        self.migration_interval = migration_interval;
+        self
+    }
+    pub fn migration_interval(&self) -> u32 {
+        self.migration_interval
+    }
 }
 
 #[wasm_bindgen]
 impl MyEvolutionaryAlgorithm {
 
     #[wasm_bindgen(constructor)]
-    pub fn new(settings: &MyEaSettings) -> Self {
+    pub fn new(settings: &MyEaSettings) -> std::result::Result<MyEvolutionaryAlgorithm, JsValue> {
         console_error_panic_hook::set_once();
 
-        MyEvolutionaryAlgorithm {
-            ea: setup_ga(settings),
-            population_stats: None,
-            prev_num_evaluations: 0,
-            prev_num_ca_steps: 0,
-            gene_counts: vec![],
-            gene_distribution: vec![],
-            cell_counts: vec![],
-            cell_distribution: vec![],
-        }
+        MyEvolutionaryAlgorithm::new_result(settings).map_err(|msg| JsValue::from_str(&msg))
     }
 
-    pub fn reset(&mut self, settings: &MyEaSettings) {
-        self.population_stats = None;
-        self.prev_num_evaluations = 0;
-        self.prev_num_ca_steps = 0;
-        self.ea = setup_ga(settings);
+    // Restores a run from a checkpoint that was obtained using save
+    pub fn load(bytes: &[u8]) -> std::result::Result<MyEvolutionaryAlgorithm, JsValue> {
+        console_error_panic_hook::set_once();
+
+        MyEvolutionaryAlgorithm::load_result(bytes).map_err(|msg| JsValue::from_str(&msg))
+    }
+
+    pub fn save(&self) -> std::result::Result<Vec<u8>, JsValue> {
+        self.save_result().map_err(|msg| JsValue::from_str(&msg))
+    }
+
+    pub fn reset(&mut self, settings: &MyEaSettings) -> std::result::Result<(), JsValue> {
+        self.reset_result(settings).map_err(|msg| JsValue::from_str(&msg))
     }
 
+    pub fn seed_width(&self) -> usize {
+        self.settings.seed_width
+    }
+
+    pub fn seed_height(&self) -> usize {
+        self.settings.seed_height
+    }
+
+    // Advances the run by one generation. With steady-state evolution, this takes as many steps
+    // as needed to create as many offspring as there are individuals
     pub fn step(&mut self) {
         self.prev_num_evaluations = self.ea.num_evaluations();
-        self.prev_num_ca_steps = self.num_ca_steps();
+        self.prev_num_ca_steps = self.total_ca_steps();
 
         self.ea.step();
+        self.exchange_opponents();
 
-        self.population_stats = self.ea.get_population_stats();
+        self.update_stats();
+    }
+
+    // The seed of the current run. Running again with this seed and the same settings gives the
+    // same results
+    pub fn seed(&self) -> u64 {
+        // The seeds of all islands are derived from the one of the first island
+        self.ea.island(0).seed()
     }
 
     pub fn num_generations(&self) -> u32 {
@@ -527,17 +1602,19 @@ impl MyEvolutionaryAlgorithm {
         self.ea.num_evaluations() - self.prev_num_evaluations
     }
 
-    pub fn num_ca_steps(&self) -> u32 {
-        match self.ea.evaluator().as_any().downcast_ref::<MyEvaluator>() {
-            Some(my_evaluator) => my_evaluator.num_ca_steps(),
-            None => panic!("Expected MyEvaluator as evaluator")
-        }
+    // The number of steps can exceed the range of u32 in long runs. It is therefore returned as
+    // a JavaScript number, which represents integers exactly up to 2^53.
+    pub fn num_ca_steps(&self) -> f64 {
+        self.total_ca_steps() as f64
     }
 
     pub fn ca_steps_delta(&self) -> u32 {
-        self.num_ca_steps() - self.prev_num_ca_steps
+        let delta = self.total_ca_steps() - self.prev_num_ca_steps;
+        u32::try_from(delta).unwrap_or(u32::MAX)
     }
 
+    // In multi-objective runs, the fitness is merely the first objective, which is negated when
+    // it is minimised. Use objective_best and objective_avg to report progress instead
     pub fn max_fitness(&self) -> f32 {
         if let Some(stats) = &self.population_stats {
             stats.max_fitness
@@ -554,6 +1631,26 @@ impl MyEvolutionaryAlgorithm {
         }
     }
 
+    pub fn num_islands(&self) -> usize {
+        self.ea.num_islands()
+    }
+
+    pub fn island_max_fitness(&self, island: usize) -> f32 {
+        if let Some(Some(stats)) = self.island_stats.get(island) {
+            stats.max_fitness
+        } else {
+            0.0
+        }
+    }
+
+    pub fn island_avg_fitness(&self, island: usize) -> f32 {
+        if let Some(Some(stats)) = self.island_stats.get(island) {
+            stats.avg_fitness
+        } else {
+            0.0
+        }
+    }
+
     pub fn best_phenotype(&self) -> String {
         if let Some(stats) = &self.population_stats {
             if let Some(phenotype) = &stats.best_indiv.phenotype() {
@@ -567,6 +1664,16 @@ impl MyEvolutionaryAlgorithm {
         String::from("None")
     }
 
+    // Returns the best phenotype as RLE pattern, so that it can be imported into tools like Golly
+    pub fn best_phenotype_rle(&self) -> String {
+        if let Some(stats) = &self.population_stats {
+            if let Some(phenotype) = &stats.best_indiv.phenotype() {
+                return phenotype.bit_grid.to_rle()
+            }
+        }
+        String::from("")
+    }
+
     pub fn best_genotype(&self) -> String {
         if let Some(stats) = &self.population_stats {
             stats.best_indiv.genotype().bits
@@ -584,11 +1691,11 @@ impl MyEvolutionaryAlgorithm {
 
         self.gene_counts.clear();
         self.gene_counts.extend(
-            (0..self.ea.population().get_individual(0).genotype().bits.len())
+            (0..self.ea.island(0).population().get_individual(0).genotype().bits.len())
                 .map(|_| 0)
         );
 
-        for indiv in self.ea.population().iter() {
+        for indiv in self.ea.individuals() {
             let genotype = indiv.genotype();
             num_genotypes += 1;
 
@@ -617,13 +1724,15 @@ impl MyEvolutionaryAlgorithm {
         let mut num_phenotypes = 0;
 
         self.cell_counts.clear();
-        self.cell_counts.extend((0..TOTAL_SEED_CELLS).map(|_| 0));
+        self.cell_counts.extend(
+            (0..self.settings.seed_width * self.settings.seed_height).map(|_| 0)
+        );
 
-        for indiv in self.ea.population().iter() {
+        for indiv in self.ea.individuals() {
             if let Some(phenotype) = indiv.phenotype() {
                 let mut cell_index = 0;
-                for y in 0..SEED_PATCH_SIZE {
-                    for x in 0..SEED_PATCH_SIZE {
+                for y in 0..self.settings.seed_height {
+                    for x in 0..self.settings.seed_width {
                         if phenotype.bit_grid.get(x, y) {
                             self.cell_counts[cell_index] += 1;
                         }
@@ -648,4 +1757,337 @@ impl MyEvolutionaryAlgorithm {
     pub fn phenotype_len(&self) -> u32 {
         self.cell_counts.len() as u32
     }
-}
\ No newline at end of file
+
+    pub fn num_objectives(&self) -> u32 {
+        self.ea.island(0).evaluator().num_objectives() as u32
+    }
+
+    // Returns the objectives of the individuals in the Pareto front. For each, it contains
+    // num_objectives values. Objectives that are minimised are negated.
+    pub fn pareto_front(&mut self) -> *const f32 {
+        self.pareto_front.clear();
+        for indiv in self.ea.pareto_front() {
+            self.pareto_front.extend_from_slice(indiv.objectives().unwrap());
+        }
+
+        self.pareto_front.as_ptr()
+    }
+
+    // The number of values returned by pareto_front
+    pub fn pareto_front_len(&self) -> u32 {
+        self.pareto_front.len() as u32
+    }
+
+    pub fn objective_name(&self, index: usize) -> String {
+        format!("{:?}", self.settings.objectives[index].0)
+    }
+
+    // Returns the values of the objective over all evaluated individuals. Unlike in the objective
+    // vectors, minimised objectives are not negated
+    fn objective_values(&self, index: usize) -> impl Iterator<Item = f32> + '_ {
+        let sign = if self.settings.objectives[index].1 { 1.0 } else { -1.0 };
+
+        self.ea.individuals().filter_map(
+            move |indiv| indiv.objectives().map(|objectives| objectives[index] * sign)
+        )
+    }
+
+    // The best value of the objective in the population, i.e. the lowest when it is minimised
+    pub fn objective_best(&self, index: usize) -> f32 {
+        let values = self.objective_values(index);
+        let best = if self.settings.objectives[index].1 {
+            values.fold(f32::NEG_INFINITY, f32::max)
+        } else {
+            values.fold(f32::INFINITY, f32::min)
+        };
+
+        if best.is_finite() { best } else { 0.0 }
+    }
+
+    pub fn objective_avg(&self, index: usize) -> f32 {
+        let (sum, count) = self.objective_values(index).fold(
+            (0.0, 0), |(sum, count), value| (sum + value, count + 1)
+        );
+
+        if count > 0 { sum / count as f32 } else { 0.0 }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small_settings() -> MyEaSettings {
+        MyEaSettings::new().set_garden_size(24).set_population_size(16).set_seed(Some(7))
+    }
+
+    fn run(settings: &MyEaSettings, num_generations: u32) -> MyEvolutionaryAlgorithm {
+        let mut ea = MyEvolutionaryAlgorithm::new_result(settings).unwrap();
+        for _ in 0..num_generations {
+            ea.step();
+        }
+        ea
+    }
+
+    // Returns the genotype and fitness of all individuals, so that runs can be compared
+    fn population(ea: &MyEvolutionaryAlgorithm) -> Vec<(bit_vec::BitVec, Option<f32>)> {
+        ea.ea().individuals().map(
+            |indiv| (indiv.genotype().bits.clone(), indiv.fitness())
+        ).collect()
+    }
+
+    #[test]
+    fn seeded_runs_are_reproducible() {
+        let settings = small_settings();
+        assert_eq!(population(&run(&settings, 5)), population(&run(&settings, 5)));
+
+        let other_settings = small_settings().set_seed(Some(8));
+        assert_ne!(population(&run(&settings, 5)), population(&run(&other_settings, 5)));
+    }
+
+    #[test]
+    fn seeded_steady_state_runs_are_reproducible() {
+        let settings = small_settings().set_steady_state(true);
+        assert_eq!(population(&run(&settings, 5)), population(&run(&settings, 5)));
+    }
+
+    // Evaluating a generation on multiple threads should give the same fitness as evaluating
+    // each seed on its own, so that parallel runs are reproducible as well
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    #[test]
+    fn parallel_evaluation_matches_sequential_evaluation() {
+        let settings = small_settings().set_population_size(64);
+        let ea = run(&settings, 3);
+        assert_eq!(population(&ea), population(&run(&settings, 3)));
+
+        let mut evaluator = MyEvaluator::new(
+            settings.garden_width,
+            settings.garden_height,
+            settings.seed_position(),
+            settings.border,
+            settings.rule,
+            Box::new(WeightedFitness::new(settings.fitness_weights))
+        );
+        for indiv in ea.ea().individuals() {
+            let phenotype = indiv.phenotype().unwrap();
+            assert_eq!(indiv.fitness(), Some(evaluator.evaluate(phenotype)));
+        }
+    }
+
+    // Checks that a run resumed from a checkpoint continues exactly like the original run
+    fn assert_resumes_exactly(settings: &MyEaSettings) {
+        let mut ea = run(settings, 3);
+        let checkpoint = ea.save_result().unwrap();
+        for _ in 0..3 {
+            ea.step();
+        }
+
+        let mut resumed = MyEvolutionaryAlgorithm::load_result(&checkpoint).unwrap();
+        assert_eq!(resumed.num_generations(), 3);
+        for _ in 0..3 {
+            resumed.step();
+        }
+
+        assert_eq!(population(&resumed), population(&ea));
+        assert_eq!(resumed.num_generations(), ea.num_generations());
+        assert_eq!(resumed.num_evaluations(), ea.num_evaluations());
+        assert_eq!(resumed.num_ca_steps(), ea.num_ca_steps());
+    }
+
+    #[test]
+    fn resume_generational_run() {
+        assert_resumes_exactly(&small_settings());
+    }
+
+    #[test]
+    fn resume_steady_state_run() {
+        assert_resumes_exactly(&small_settings().set_steady_state(true));
+    }
+
+    #[test]
+    fn resume_island_run() {
+        assert_resumes_exactly(&small_settings()
+            .set_num_islands(3)
+            .set_topology(Topology::Random)
+            .set_migrant_selection(MigrantSelection::Random)
+            .set_migration_interval(2));
+    }
+
+    #[test]
+    fn resume_coevolution_run() {
+        assert_resumes_exactly(&small_settings()
+            .set_coevolution(true)
+            .set_pairing(Pairing::Sampled)
+            .set_num_opponents(2)
+            .set_num_battle_steps(50));
+    }
+
+    #[test]
+    fn resume_two_population_run() {
+        assert_resumes_exactly(&small_settings()
+            .set_coevolution(true)
+            .set_two_populations(true)
+            .set_num_islands(2)
+            .set_num_battle_steps(50));
+    }
+
+    #[test]
+    fn load_rejects_invalid_checkpoints() {
+        let checkpoint = run(&small_settings(), 1).save_result().unwrap();
+
+        assert!(MyEvolutionaryAlgorithm::load_result(&checkpoint[..checkpoint.len() / 2]).is_err());
+    }
+
+    #[test]
+    fn objective_stats_are_reported_unnegated() {
+        let settings = small_settings()
+            .add_objective(Objective::NumStartCells, false)
+            .add_objective(Objective::MaxAliveCells, true);
+        let ea = run(&settings, 2);
+
+        assert_eq!(ea.num_objectives(), 2);
+        assert_eq!(ea.objective_name(0), "NumStartCells");
+        let start_cells: Vec<f32> = ea.ea().individuals().map(
+            |indiv| -indiv.objectives().unwrap()[0]
+        ).collect();
+        assert!(start_cells.iter().all(|&value| value >= 0.0));
+        let min = start_cells.iter().copied().fold(f32::INFINITY, f32::min);
+        assert_eq!(ea.objective_best(0), min);
+        let avg = start_cells.iter().sum::<f32>() / start_cells.len() as f32;
+        assert!((ea.objective_avg(0) - avg).abs() < 1e-3);
+        assert!(ea.objective_best(1) >= ea.objective_avg(1));
+    }
+
+    #[test]
+    fn default_settings_are_valid() {
+        assert_eq!(MyEaSettings::new().validate_result(), Ok(()));
+    }
+
+    #[test]
+    fn validate_garden_and_seed_sizes() {
+        let settings = MyEaSettings::new().set_seed_width(0);
+        assert!(settings.validate_result().is_err());
+
+        // The garden should be large enough for the CA, even when the seed fits
+        let settings = MyEaSettings::new().set_garden_size(2).set_seed_width(1).set_seed_height(1);
+        assert!(settings.validate_result().is_err());
+        assert!(setup_ga(&settings).is_err());
+
+        let settings = MyEaSettings::new().set_garden_size(3).set_seed_width(1).set_seed_height(1);
+        assert_eq!(settings.validate_result(), Ok(()));
+
+        let settings = MyEaSettings::new().set_garden_width(7);
+        assert!(settings.validate_result().is_err());
+
+        let settings = MyEaSettings::new().set_seed_offset(56, 0);
+        assert_eq!(settings.validate_result(), Ok(()));
+        let settings = settings.set_seed_offset(57, 0);
+        assert!(settings.validate_result().is_err());
+        let settings = settings.set_seed_offset(0, 57);
+        assert!(settings.validate_result().is_err());
+    }
+
+    #[test]
+    fn validate_number_of_migrants() {
+        let settings = MyEaSettings::new().set_num_islands(3).set_num_migrants(50);
+        assert_eq!(settings.clone().set_topology(Topology::Ring).validate_result(), Ok(()));
+
+        // All other islands may send their migrants to the same island
+        assert!(settings.clone().set_topology(Topology::Random).validate_result().is_err());
+        assert!(settings.set_topology(Topology::FullyConnected).validate_result().is_err());
+    }
+
+    #[test]
+    fn steady_state_does_not_support_coevolution() {
+        let settings = MyEaSettings::new().set_garden_size(32).set_coevolution(true);
+        assert_eq!(settings.validate_result(), Ok(()));
+        assert!(settings.set_steady_state(true).validate_result().is_err());
+    }
+
+    #[test]
+    fn two_populations_require_coevolution_on_two_islands() {
+        let settings = MyEaSettings::new().set_garden_size(32).set_two_populations(true);
+        assert!(settings.clone().set_num_islands(2).validate_result().is_err());
+        assert!(settings.clone().set_coevolution(true).validate_result().is_err());
+        assert_eq!(settings.set_coevolution(true).set_num_islands(2).validate_result(), Ok(()));
+    }
+
+    #[test]
+    fn round_robin_alternates_roles() {
+        let mut battle_evaluator = BattleEvaluator::new(
+            &MyEaSettings::new().set_garden_size(32).set_coevolution(true), 0
+        ).unwrap();
+        let pairs = battle_evaluator.pairs(5);
+
+        assert_eq!(pairs.len(), 10);
+        for seed in 0..5 {
+            assert_eq!(pairs.iter().filter(|&&(first, _)| first == seed).count(), 2);
+        }
+    }
+
+    #[test]
+    fn two_populations_battle_each_other() {
+        let settings = small_settings()
+            .set_coevolution(true)
+            .set_two_populations(true)
+            .set_num_islands(2)
+            .set_num_battle_steps(50);
+        let mut ea = run(&settings, 1);
+        // Without opponents, the seeds of the first generation are all equally fit
+        assert!(ea.ea().individuals().all(|indiv| indiv.fitness() == Some(0.5)));
+
+        ea.step();
+        for island in ea.ea().islands() {
+            let battle_evaluator = island.evaluator().as_any()
+                .downcast_ref::<BattleEvaluator>().unwrap();
+            assert_eq!(battle_evaluator.opponents.as_ref().map(Vec::len), Some(16));
+            assert!(island.population().iter().all(
+                |indiv| (0.0..=1.0).contains(&indiv.fitness().unwrap())
+            ));
+        }
+        // The populations evolve separately
+        assert!(ea.ea().islands().iter().all(|island| island.population().size() == 16));
+    }
+
+    #[test]
+    fn ca_steps_are_counted_beyond_u32() {
+        let settings = small_settings().set_coevolution(true).set_num_battle_steps(50);
+        let mut ea = run(&settings, 1);
+        let num_steps = u32::MAX as u64 - 10;
+        ea.ea.islands_mut()[0].evaluator_mut().as_any_mut()
+            .downcast_mut::<BattleEvaluator>().unwrap().set_num_ca_steps(num_steps);
+        ea.prev_num_ca_steps = num_steps;
+
+        ea.step();
+        assert!(ea.num_ca_steps() > u32::MAX as f64);
+        assert_eq!(ea.ca_steps_delta() as f64, ea.num_ca_steps() - num_steps as f64);
+    }
+
+    #[test]
+    fn distinct_objects_are_only_counted_for_conway() {
+        let mut gol = GameOfLife::new_result(16, 16, GridBorder::Zeroes).unwrap();
+        gol.place_pattern_result("block", 2, 2, ca::Transform::Identity).unwrap();
+        gol.place_pattern_result("blinker", 8, 8, ca::Transform::Identity).unwrap();
+        assert_eq!(num_distinct_objects(&gol), 2);
+
+        // Blocks and blinkers behave the same in HighLife, but the census does not know that
+        gol.set_rule("B36/S23".parse().unwrap());
+        assert_eq!(num_distinct_objects(&gol), 0);
+    }
+
+    #[test]
+    fn seed_anchors() {
+        let settings = MyEaSettings::new()
+            .set_garden_width(10)
+            .set_garden_height(8)
+            .set_seed_width(4)
+            .set_seed_height(2);
+        let position = |anchor| settings.clone().set_seed_anchor(anchor).seed_position();
+
+        assert_eq!(position(SeedAnchor::Center), (3, 3));
+        assert_eq!(position(SeedAnchor::TopLeft), (0, 0));
+        assert_eq!(position(SeedAnchor::TopRight), (6, 0));
+        assert_eq!(position(SeedAnchor::BottomLeft), (0, 6));
+        assert_eq!(position(SeedAnchor::BottomRight), (6, 6));
+        assert_eq!(settings.clone().set_seed_offset(1, 5).seed_position(), (1, 5));
+    }
+}
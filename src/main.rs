@@ -2,7 +2,7 @@ use ga_of_life::{MyEvolutionaryAlgorithm, MyEaSettings};
 
 fn main() {
     let ga_settings = MyEaSettings::new();
-    let mut ga = MyEvolutionaryAlgorithm::new(&ga_settings);
+    let mut ga = MyEvolutionaryAlgorithm::new_result(&ga_settings).expect("Invalid settings");
     let mut max_fitness = 0.0;
 
     for _ in 0..100 {
@@ -39,7 +39,9 @@ function App() {
                                     <Ea/>
                                 </Route>
                                 <Route exact path="/ca">
-                                    <Ca seed={eaControl?.eaState?.bestPhenotype} />
+                                    <Ca seed={eaControl?.eaState?.bestPhenotype}
+                                        seedWidth={eaControl?.eaState?.seedWidth}
+                                        seedHeight={eaControl?.eaState?.seedHeight} />
                                 </Route>
                                 <Route exact path="/help">
                                     <Help />
@@ -1,10 +1,11 @@
-import React, { useCallback, useContext, useEffect, useRef } from 'react';
+import React, { useCallback, useContext, useEffect, useRef, useState } from 'react';
 import Button from 'react-bootstrap/Button';
 import Col from 'react-bootstrap/Col';
 import Container from 'react-bootstrap/Container';
+import Form from 'react-bootstrap/Form';
 import Row from 'react-bootstrap/Row';
 
-import { MAX_GRID_SIZE, SEED_SIZE } from '../shared/Constants';
+import { MAX_GRID_SIZE } from '../shared/Constants';
 import { bound } from '../shared/utils';
 
 const GRID_COLOR = "#CCCCCC";
@@ -12,19 +13,39 @@ const EMPTY_COLOR = "#FFFFFF";
 const ALIVE_COLOR = "#000000";
 const LIVED_COLOR = "#A0A0A0";
 
+// Patterns from the catalog are placed with their top-left corner at this position
+const PATTERN_POS = 2;
+
+const TRANSFORMS = [
+    'Identity', 'Rotate90', 'Rotate180', 'Rotate270',
+    'FlipHorizontal', 'FlipVertical', 'FlipDiagonal', 'FlipAntiDiagonal'
+];
+
+// The ways in which the cells at the edges of the grid interact with the outside. The names match
+// the GridBorder enum of the WASM module
+export const BORDERS = [
+    { name: 'Zeroes', label: 'Dead cells' },
+    { name: 'Wrapped', label: 'Wrapped (torus)' },
+    { name: 'Absorbing', label: 'Absorbing' },
+    { name: 'KleinBottle', label: 'Klein bottle' },
+    { name: 'ProjectivePlane', label: 'Projective plane' },
+    { name: 'Mirror', label: 'Mirror' },
+    { name: 'Alive', label: 'Live cells' },
+];
+
 export const CaSettingsContext = React.createContext();
 export const CaControlContext = React.createContext();
 
 export const initialCaSettings = {
-    borderWraps: false,
+    border: 'Zeroes',
     gridSize: 64,
 };
 export const initialCaControlState = undefined;
 
 export function caSettingsReducer(state, action) {
     switch (action.type) {
-        case 'borderWraps': return {
-            ...state, borderWraps: action.value
+        case 'border': return {
+            ...state, border: action.value
         };
         case 'gridSize': return {
             ...state, gridSize: bound(action.value, 32, MAX_GRID_SIZE)
@@ -67,16 +88,22 @@ export function caControlReducer(state, action) {
             numSteps: state.numSteps + 1,
             caStats: action.stats,
         };
+        case 'placedPattern': return {
+            ...state,
+            caStats: action.stats,
+        };
         default:
             console.error('Unexpected action:', action.type);
     }
 }
 
-export function CaControl({ seed }) {
+export function CaControl({ seed, seedWidth, seedHeight }) {
     const { caControl, caControlDispatch } = useContext(CaControlContext);
     const { caSettings } = useContext(CaSettingsContext);
     const onceAliveRef = useRef(new Array(caSettings.gridSize * caSettings.gridSize));
     const canvasRef = useRef(null);
+    const [patternName, setPatternName] = useState('glider');
+    const [transform, setTransform] = useState('Identity');
 
     const ctx = canvasRef.current?.getContext('2d');
     const ca = caControl?.ca;
@@ -90,17 +117,17 @@ export function CaControl({ seed }) {
         (ca) => {
             ca.reset();
 
-            const x0 = (gridWidth - SEED_SIZE) / 2;
-            const y0 = (gridHeight - SEED_SIZE) / 2;
-            for (let x = 0; x < SEED_SIZE; x++) {
-                for (let y = 0; y < SEED_SIZE; y++) {
-                    if (seed.charAt(x + y * SEED_SIZE) !== '0') {
+            const x0 = Math.floor((gridWidth - seedWidth) / 2);
+            const y0 = Math.floor((gridHeight - seedHeight) / 2);
+            for (let x = 0; x < seedWidth; x++) {
+                for (let y = 0; y < seedHeight; y++) {
+                    if (seed.charAt(x + y * seedWidth) !== '0') {
                         ca.set(x + x0, y + y0);
                     }
                 }
             }
         },
-        [seed, gridWidth, gridHeight]
+        [seed, seedWidth, seedHeight, gridWidth, gridHeight]
     )
 
     const drawGrid = useCallback(
@@ -165,7 +192,11 @@ export function CaControl({ seed }) {
                 }
             }
 
-            return { numAlive, numOnceAlive: onceAlive.filter(x => x).length };
+            const census = ca.census();
+            const objects = census.summary();
+            census.free();
+
+            return { numAlive, numOnceAlive: onceAlive.filter(x => x).length, objects };
         },
         [gridWidth, gridHeight, ca, onceAlive]
     )
@@ -181,6 +212,24 @@ export function CaControl({ seed }) {
         [ca, drawCells, caControlDispatch, updateCaStats]
     )
 
+    const placePattern = useCallback(
+        () => {
+            try {
+                ca.place_pattern(
+                    patternName, PATTERN_POS, PATTERN_POS, wasm.Transform[transform]
+                );
+            } catch (error) {
+                console.error('Failed to place pattern:', error);
+                return;
+            }
+            const stats = updateCaStats();
+            drawCells();
+
+            caControlDispatch({ type: 'placedPattern', stats });
+        },
+        [ca, patternName, transform, drawCells, caControlDispatch, updateCaStats]
+    );
+
     const clearOnceAlive = useCallback(
         () => {
             onceAlive.forEach((_, i, a) => { a[i] = 0; });
@@ -199,7 +248,9 @@ export function CaControl({ seed }) {
     useEffect(() => {
         async function init() {
             const wasm = await wasmInit();
-            const ca = new wasm.GameOfLife(caSettings.gridSize, caSettings.gridSize, caSettings.borderWraps);
+            const ca = wasm.GameOfLife.with_border(
+                caSettings.gridSize, caSettings.gridSize, wasm.GridBorder[caSettings.border]
+            );
             seedCa(ca);
             clearOnceAlive();
             caControlDispatch({ type: 'initializing', ca, seed });
@@ -241,6 +292,25 @@ export function CaControl({ seed }) {
                 <Button onClick={() => reset()} disabled={!caControl || caControl.autoRun}>Reset</Button>
             </Col>
         </Row>
+        <Row className="ButtonRow">
+            <Col xs="auto">
+                <Form.Control as="select" value={patternName} disabled={!wasm}
+                    onChange={(e) => setPatternName(e.target.value)}>
+                    {(wasm ? wasm.pattern_names() : []).map(name =>
+                        <option key={name} value={name}>{name}</option>
+                    )}
+                </Form.Control>
+            </Col>
+            <Col xs="auto">
+                <Form.Control as="select" value={transform}
+                    onChange={(e) => setTransform(e.target.value)}>
+                    {TRANSFORMS.map(name => <option key={name} value={name}>{name}</option>)}
+                </Form.Control>
+            </Col>
+            <Col xs="auto">
+                <Button onClick={() => placePattern()} disabled={!caControl || caControl.autoRun}>Add pattern</Button>
+            </Col>
+        </Row>
         <Row>
             <Col>
                 <canvas ref={canvasRef}
@@ -248,5 +318,8 @@ export function CaControl({ seed }) {
                     height={(cellSize + 1) * caSettings.gridSize + 1}></canvas>
             </Col>
         </Row>
+        <Row>
+            <Col>Objects: {caControl?.caStats?.objects || 'None'}</Col>
+        </Row>
     </Container>);
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
 import { useContext, useEffect, useState } from 'react';
 
 import { EaControlContext } from '../components/EaControl';
+import { objectiveLabel } from '../components/EaSettings';
 
 const Highcharts = require('highcharts');
 require('highcharts/modules/exporting')(Highcharts);
@@ -67,7 +68,14 @@ export function EaGraph() {
             // Add EA state to graph, if not yet already done.
             if (eaState?.generations !== lastPlotGeneration) {
                 const shift = numPlotPoints === historyLen;
-                chart.series[0].addPoint([eaState.generations, eaState.maxFitness], true, shift);
+                // Fitness is not meaningful for multi-objective runs, so show the first objective
+                const progress = eaState.objectives[0];
+                if (progress && lastPlotGeneration === 0) {
+                    chart.series[0].update({ name: `Best: ${objectiveLabel(progress.name)}` });
+                }
+                chart.series[0].addPoint(
+                    [eaState.generations, progress ? progress.best : eaState.maxFitness], true, shift
+                );
                 chart.series[1].addPoint([eaState.generations, eaState.evaluationsDelta], true, shift);
 
                 setLastPlotGeneration(eaState.generations);
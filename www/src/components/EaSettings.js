@@ -6,12 +6,32 @@ import Row from 'react-bootstrap/Row';
 import React, { useContext } from 'react';
 
 import { EaControlContext } from './EaControl';
-import { CaSettingsContext } from './CaControl';
+import { BORDERS, CaSettingsContext } from './CaControl';
 import { bound } from '../shared/utils';
 
 export const EaSettingsContext = React.createContext();
 
+// The objectives that can be optimised simultaneously using NSGA-II. The names match the
+// Objective enum of the WASM module
+export const OBJECTIVES = [
+    { name: 'NumToggledCells', label: 'Number of toggled cells' },
+    { name: 'NumToggledSteps', label: 'Steps to reach toggled cells' },
+    { name: 'MaxAliveCells', label: 'Maximum alive cells' },
+    { name: 'MaxAliveSteps', label: 'Step to reach maximum' },
+    { name: 'NumStartCells', label: 'Number of cells at start' },
+    { name: 'CyclePeriod', label: 'Cycle period' },
+    { name: 'MovingObject', label: 'Moving object' },
+    { name: 'DistinctObjects', label: 'Distinct objects' },
+    { name: 'EscapedSpaceships', label: 'Escaped spaceships' },
+];
+
+export function objectiveLabel(name) {
+    return OBJECTIVES.find(objective => objective.name === name)?.label ?? name;
+}
+
 export const initialEaSettings = {
+    seedWidth: 8,
+    seedHeight: 8,
     populationSize: 100,
     recombinationRate: 0.4,
     mutationRate: 0.9,
@@ -22,11 +42,19 @@ export const initialEaSettings = {
     fitnessMaxAliveCells: 0.0,
     fitnessMaxAliveSteps: 0.0,
     fitnessNumStartCells: 0.0,
+    // For each objective, whether it is ignored, maximised or minimised
+    objectives: Object.fromEntries(OBJECTIVES.map(objective => [objective.name, 'ignore'])),
 };
 
 export function eaSettingsReducer(state, action) {
     console.log("dispatched", state, action);
     switch (action.type) {
+        case 'seedWidth': return {
+            ...state, seedWidth: bound(action.value, 1, 16)
+        };
+        case 'seedHeight': return {
+            ...state, seedHeight: bound(action.value, 1, 16)
+        };
         case 'populationSize': return {
             ...state, populationSize: bound(action.value, 10, 1000)
         };
@@ -57,6 +85,9 @@ export function eaSettingsReducer(state, action) {
         case 'fitnessNumStartCells': return {
             ...state, fitnessNumStartCells: bound(action.value, -100, 100)
         };
+        case 'objective': return {
+            ...state, objectives: { ...state.objectives, [action.objective]: action.value }
+        };
         default:
             console.error('Unexpected action:', action.type);
     }
@@ -115,6 +146,26 @@ export function EaSettings() {
         );
     };
 
+    function ObjectiveFormField(objective) {
+        const id = `formObjective${objective.name}`;
+
+        return (
+            <Form.Group as={Row} controlId={id} key={id}>
+                <Form.Label column xs={8}>{objective.label}</Form.Label>
+                <Col xs={4}>
+                    <Form.Control as="select" value={eaSettings.objectives[objective.name]}
+                        onChange={e => eaSettingsDispatch({
+                            type: 'objective', objective: objective.name, value: e.target.value
+                        })}>
+                        <option value="ignore">Ignore</option>
+                        <option value="maximise">Maximise</option>
+                        <option value="minimise">Minimise</option>
+                    </Form.Control>
+                </Col>
+            </Form.Group>
+        );
+    };
+
     const maxAliveFitnessEnabled = Math.abs(eaSettings.fitnessMaxAliveCells) >= 0.01;
     const numToggledFitnessEnabled = Math.abs(eaSettings.fitnessNumToggledCells) >= 0.01;
     
@@ -135,14 +186,28 @@ export function EaSettings() {
                         stepSize: 16,
                     }
                 )}
-                { CheckBoxFormField(
-                    'formBorderWraps',
-                    'Enable border wrap',
-                    caSettings.borderWraps,
-                    'borderWraps',
-                    {
-                        dispatch: caSettingsDispatch
-                    }
+                <Form.Group as={Row} controlId="formBorder">
+                    <Form.Label column xs={8}>Border</Form.Label>
+                    <Col xs={4}>
+                        <Form.Control as="select" value={caSettings.border}
+                            onChange={e => caSettingsDispatch({ type: 'border', value: e.target.value })}>
+                            {BORDERS.map(border =>
+                                <option key={border.name} value={border.name}>{border.label}</option>
+                            )}
+                        </Form.Control>
+                    </Col>
+                </Form.Group>
+                { NumericFormField(
+                    'formSeedWidth',
+                    'Seed width',
+                    eaSettings.seedWidth,
+                    'seedWidth'
+                )}
+                { NumericFormField(
+                    'formSeedHeight',
+                    'Seed height',
+                    eaSettings.seedHeight,
+                    'seedHeight'
                 )}
             </Col>
         </Row>
@@ -189,6 +254,16 @@ export function EaSettings() {
                 )}
             </Col>
         </Row>
+        <Row className="SettingsRow pt-2 mt-2 mb-2">
+            <Col sm={12} md={3}><h5>Objectives</h5></Col>
+            <Col sm={12} md={9}>
+                <p>
+                    When at least two objectives are selected, these are optimised simultaneously
+                    using NSGA-II instead of combining them using the fitness weights above.
+                </p>
+                { OBJECTIVES.map(objective => ObjectiveFormField(objective)) }
+            </Col>
+        </Row>
         <Row className="SettingsRow pt-2 mt-2 mb-2">
             <Col sm={12} md={3}><h5>Solver</h5></Col>
             <Col sm={12} md={9}>
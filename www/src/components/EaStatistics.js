@@ -1,10 +1,11 @@
-import { useContext } from 'react';
+import React, { useContext } from 'react';
 
 import Col from 'react-bootstrap/Col';
 import Container from 'react-bootstrap/Container';
 import Row from 'react-bootstrap/Row';
 
 import { EaControlContext } from './EaControl';
+import { objectiveLabel } from './EaSettings';
 
 export function EaStatistics() {
     const { eaControl } = useContext(EaControlContext);
@@ -29,14 +30,26 @@ export function EaStatistics() {
             <Col className="Label" xs={8}>Num. CA steps</Col>
             <Col className="NumValue" xs={4}>{eaState.caSteps}</Col>
         </Row>
-        <Row>
-            <Col className="Label" xs={8}>Max. fitness</Col>
-            <Col className="NumValue" xs={4}>{Math.round(eaState.maxFitness)}</Col>
-        </Row>
-        <Row>
-            <Col className="Label" xs={8}>Avg. fitness</Col>
-            <Col className="NumValue" xs={4}>{Math.round(eaState.avgFitness)}</Col>
-        </Row>
+        { eaState.objectives.length === 0 && <>
+            <Row>
+                <Col className="Label" xs={8}>Max. fitness</Col>
+                <Col className="NumValue" xs={4}>{Math.round(eaState.maxFitness)}</Col>
+            </Row>
+            <Row>
+                <Col className="Label" xs={8}>Avg. fitness</Col>
+                <Col className="NumValue" xs={4}>{Math.round(eaState.avgFitness)}</Col>
+            </Row>
+        </> }
+        { eaState.objectives.map(objective => <React.Fragment key={objective.name}>
+            <Row>
+                <Col className="Label" xs={8}>Best: {objectiveLabel(objective.name)}</Col>
+                <Col className="NumValue" xs={4}>{Math.round(objective.best)}</Col>
+            </Row>
+            <Row>
+                <Col className="Label" xs={8}>Avg: {objectiveLabel(objective.name)}</Col>
+                <Col className="NumValue" xs={4}>{Math.round(objective.avg)}</Col>
+            </Row>
+        </React.Fragment>) }
         <Row>
             <Col className="Label" xs={8}>Run time [s]</Col>
             <Col className="NumValue" xs={4}>{Math.round(runTimeInSeconds)}</Col>
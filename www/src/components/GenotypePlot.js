@@ -1,5 +1,6 @@
 import { useEffect, useRef, useState } from 'react';
-import { NUM_GENOTYPE_GROUPS, SEED_SIZE } from '../shared/Constants';
+import { NUM_GENOTYPE_GROUPS } from '../shared/Constants';
+import { initialEaSettings } from './EaSettings';
 
 // Separation between the big cells
 const S1 = 3;
@@ -55,7 +56,7 @@ function drawGenotype(ctx, plotSettings, genotype) {
 
         col += 1;
         index += 4;
-        if (col % SEED_SIZE === 0) {
+        if (col % plotSettings.seedWidth === 0) {
             x = 0;
             y += w + S1;
         } else {
@@ -64,29 +65,42 @@ function drawGenotype(ctx, plotSettings, genotype) {
     }
 }
 
-export function GenotypePlot({ genotype }) {
+export function GenotypePlot({
+    genotype,
+    // The seed size is only known once the run has started
+    seedWidth = initialEaSettings.seedWidth,
+    seedHeight = initialEaSettings.seedHeight
+}) {
     const [plotSettings, setPlotSettings] = useState();
     const canvasRef = useRef(null);
 
     useEffect(() => {
         const canvas = canvasRef.current;
-        if (canvas.width === plotSettings?.canvasWidth) {
+        if (
+            canvas.width === plotSettings?.canvasWidth
+            && seedWidth === plotSettings?.seedWidth
+            && seedHeight === plotSettings?.seedHeight
+        ) {
             return;
         }
 
-        const horizontalCellSpacing = (SEED_SIZE + 1) * S1;
+        // The top two rows show the group bits, which need room for NUM_GENOTYPE_GROUPS cells
+        const numCols = Math.max(seedWidth, NUM_GENOTYPE_GROUPS);
+        const horizontalCellSpacing = (numCols + 1) * S1;
         const horizontalCellSpace = canvas.width - horizontalCellSpacing;
-        const verticalCellSpacing = (SEED_SIZE + 3) * S1 + S3;
+        const verticalCellSpacing = (seedHeight + 3) * S1 + S3;
         const verticalCellSpace = canvas.height - verticalCellSpacing;
-        const bigCellMaxWidth = Math.floor(horizontalCellSpace / SEED_SIZE);
-        const bigCellMaxHeight = Math.floor(verticalCellSpace / (SEED_SIZE + 2));
+        const bigCellMaxWidth = Math.floor(horizontalCellSpace / numCols);
+        const bigCellMaxHeight = Math.floor(verticalCellSpace / (seedHeight + 2));
         const bigCellMaxSize = Math.min(bigCellMaxHeight, bigCellMaxWidth); 
         const cellSize = Math.floor((bigCellMaxSize - S2) / 2);
         const bigCellSize = cellSize * 2 + S2;
-        const width = horizontalCellSpacing + SEED_SIZE * bigCellSize;
-        const height = verticalCellSpacing + (SEED_SIZE + 2) * bigCellSize;
+        const width = horizontalCellSpacing + numCols * bigCellSize;
+        const height = verticalCellSpacing + (seedHeight + 2) * bigCellSize;
         const settings = {
             canvasWidth: canvas.width,
+            seedWidth,
+            seedHeight,
             cellSize,
             x0: Math.floor((canvas.width - width) / 2),
             y0: Math.floor((canvas.height - height) / 2),
@@ -98,7 +112,7 @@ export function GenotypePlot({ genotype }) {
         console.info("Plot settings:", settings);
 
         setPlotSettings(settings);
-    }, [plotSettings]);
+    }, [plotSettings, seedWidth, seedHeight]);
 
     useEffect(() => {
         if (!plotSettings) {
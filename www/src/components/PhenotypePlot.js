@@ -1,5 +1,5 @@
 import { useEffect, useRef, useState } from 'react';
-import { SEED_SIZE } from '../shared/Constants';
+import { initialEaSettings } from './EaSettings';
 
 // Separation between the cells
 const S1 = 3;
@@ -24,7 +24,7 @@ function drawPhenotype(ctx, plotSettings, phenotype) {
         ctx.fillRect(x + plotSettings.x0, y + plotSettings.y0, plotSettings.cellSize, plotSettings.cellSize);
 
         index += 1;
-        if (index % SEED_SIZE === 0) {
+        if (index % plotSettings.seedWidth === 0) {
             x = 0;
             y += plotSettings.cellSize + S1;
         } else {
@@ -33,25 +33,33 @@ function drawPhenotype(ctx, plotSettings, phenotype) {
     }
 }
 
-export function PhenotypePlot({ phenotype }) {
+export function PhenotypePlot({
+    phenotype,
+    // The seed size is only known once the run has started
+    seedWidth = initialEaSettings.seedWidth,
+    seedHeight = initialEaSettings.seedHeight
+}) {
     const [plotSettings, setPlotSettings] = useState();
     const canvasRef = useRef(null);
 
     useEffect(() => {
-        if (plotSettings) {
+        if (plotSettings?.seedWidth === seedWidth && plotSettings?.seedHeight === seedHeight) {
             return;
         }
 
         const canvas = canvasRef.current;
-        const cellSpacing = (SEED_SIZE - 1) * S1;
-        const horizontalCellSpace = canvas.width - cellSpacing;
-        const verticalCellSpace = canvas.height - cellSpacing;
-        const cellMaxWidth = Math.floor(horizontalCellSpace / SEED_SIZE);
-        const cellMaxHeight = Math.floor(verticalCellSpace / SEED_SIZE);
+        const horizontalCellSpacing = (seedWidth - 1) * S1;
+        const verticalCellSpacing = (seedHeight - 1) * S1;
+        const horizontalCellSpace = canvas.width - horizontalCellSpacing;
+        const verticalCellSpace = canvas.height - verticalCellSpacing;
+        const cellMaxWidth = Math.floor(horizontalCellSpace / seedWidth);
+        const cellMaxHeight = Math.floor(verticalCellSpace / seedHeight);
         const cellSize = Math.min(cellMaxHeight, cellMaxWidth); 
-        const width = cellSpacing + SEED_SIZE * cellSize;
-        const height = cellSpacing + SEED_SIZE * cellSize;
+        const width = horizontalCellSpacing + seedWidth * cellSize;
+        const height = verticalCellSpacing + seedHeight * cellSize;
         const settings = {
+            seedWidth,
+            seedHeight,
             cellSize,
             x0: Math.floor((canvas.width - width) / 2),
             y0: Math.floor((canvas.height - height) / 2),
@@ -62,7 +70,7 @@ export function PhenotypePlot({ phenotype }) {
         console.info("Plot settings:", settings);
 
         setPlotSettings(settings);
-    }, [plotSettings]);
+    }, [plotSettings, seedWidth, seedHeight]);
 
     useEffect(() => {
         if (!plotSettings) {
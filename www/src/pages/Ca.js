@@ -9,7 +9,7 @@ import { useHistory } from "react-router-dom";
 import { CaControl, CaControlContext, caControlReducer, initialCaControlState } from '../components/CaControl';
 import { CaGraph } from '../components/CaGraph';
 
-export function Ca({ seed }) {
+export function Ca({ seed, seedWidth, seedHeight }) {
     const history = useHistory();
     const [caControl, caControlDispatch] = useReducer(caControlReducer, initialCaControlState);
 
@@ -22,7 +22,7 @@ export function Ca({ seed }) {
         <Row>
             <CaControlContext.Provider value={{ caControl, caControlDispatch }}>
                 <Col>
-                    <CaControl seed={seed} />
+                    <CaControl seed={seed} seedWidth={seedWidth} seedHeight={seedHeight} />
                 </Col>
                 <Col>
                     <CaGraph />
@@ -12,6 +12,8 @@ import { EaControl, EaControlContext } from '../components/EaControl';
 
 export function EaRunner() {
     const { eaControl } = useContext(EaControlContext);
+    const seedWidth = eaControl?.eaState?.seedWidth;
+    const seedHeight = eaControl?.eaState?.seedHeight;
 
     return (<Container>
         <Row>
@@ -32,19 +34,23 @@ export function EaRunner() {
         <Row>
             <Col lg={3}>
                 <h3>Gene distribution</h3>
-                <GenotypePlot genotype={eaControl?.eaState?.geneDistribution} />
+                <GenotypePlot genotype={eaControl?.eaState?.geneDistribution}
+                    seedWidth={seedWidth} seedHeight={seedHeight} />
             </Col>
             <Col lg={3}>
                 <h3>Best genotype</h3>
-                <GenotypePlot genotype={eaControl?.eaState?.bestGenotype} />
+                <GenotypePlot genotype={eaControl?.eaState?.bestGenotype}
+                    seedWidth={seedWidth} seedHeight={seedHeight} />
             </Col>
             <Col lg={3}>
                 <h3>Cell distribution</h3>
-                <PhenotypePlot phenotype={eaControl?.eaState?.cellDistribution} />
+                <PhenotypePlot phenotype={eaControl?.eaState?.cellDistribution}
+                    seedWidth={seedWidth} seedHeight={seedHeight} />
             </Col>
             <Col lg={3}>
                 <h3>Best phenotype</h3>
-                <PhenotypePlot phenotype={eaControl?.eaState?.bestPhenotype} />
+                <PhenotypePlot phenotype={eaControl?.eaState?.bestPhenotype}
+                    seedWidth={seedWidth} seedHeight={seedHeight} />
             </Col>
         </Row>
     </Container>);
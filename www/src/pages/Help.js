@@ -34,7 +34,7 @@ export function Help() {
         <Row>
             <p>
                 You can solve variants of the following problem:
-                    Starting with an 8x8 group of cells at the center of the CA grid, what is the configuration
+                    Starting with a small group of cells (8x8 by default) at the center of the CA grid, what is the configuration
                     that covers most of the grid?
             </p>
             <p>
@@ -1,4 +1,3 @@
 export const MAX_GRID_SIZE = 128;
-export const SEED_SIZE = 8;
 
 export const NUM_GENOTYPE_GROUPS = 8;
\ No newline at end of file
@@ -6,9 +6,11 @@ let ea;
 // A bit of a pain to explictly initialize WASM object this way, but there does not seem a much
 // nicer solution, given that the redux state should be immutable.
 function settings_to_wasm(settings) {
-    const ea_settings = new wasm.MyEaSettings()
-        .set_border(settings.borderWraps)
+    let ea_settings = new wasm.MyEaSettings()
+        .set_border(wasm.GridBorder[settings.border])
         .set_garden_size(settings.gridSize)
+        .set_seed_width(settings.seedWidth)
+        .set_seed_height(settings.seedHeight)
         .set_mutation_rate(settings.mutationRate)
         .set_recombination_rate(settings.recombinationRate)
         .set_population_size(settings.populationSize)
@@ -18,7 +20,15 @@ function settings_to_wasm(settings) {
         .set_fw_num_toggled_steps(settings.fitnessNumToggledSteps)
         .set_fw_max_alive_cells(settings.fitnessMaxAliveCells)
         .set_fw_max_alive_steps(settings.fitnessMaxAliveSteps)
-        .set_fw_num_start_cells(settings.fitnessNumStartCells)
+        .set_fw_num_start_cells(settings.fitnessNumStartCells);
+
+    // NSGA-II requires at least two objectives. Otherwise, the fitness weights are used
+    const objectives = Object.entries(settings.objectives).filter(([, goal]) => goal !== 'ignore');
+    if (objectives.length >= 2) {
+        for (const [objective, goal] of objectives) {
+            ea_settings = ea_settings.add_objective(wasm.Objective[objective], goal === 'maximise');
+        }
+    }
 
     return ea_settings;
 }
@@ -48,6 +58,11 @@ export function reset(settings) {
 export function step() {
     ea.step();
 
+    const islands = [...Array(ea.num_islands()).keys()];
+    // Fitness is only meaningful with a single objective. Otherwise, report each objective
+    const numObjectives = ea.num_objectives();
+    const objectives = numObjectives > 1 ? [...Array(numObjectives).keys()] : [];
+
     return {
         generations: ea.num_generations(),
         evaluations: ea.num_evaluations(),
@@ -56,9 +71,21 @@ export function step() {
         caStepsDelta: ea.ca_steps_delta(),
         maxFitness: ea.max_fitness(),
         avgFitness: ea.avg_fitness(),
+        islandMaxFitness: islands.map(island => ea.island_max_fitness(island)),
+        islandAvgFitness: islands.map(island => ea.island_avg_fitness(island)),
         bestGenotype: ea.best_genotype(),
         bestPhenotype: ea.best_phenotype(),
+        bestPhenotypeRle: ea.best_phenotype_rle(),
+        seedWidth: ea.seed_width(),
+        seedHeight: ea.seed_height(),
         geneDistribution: copyFloatArray(wasm_bg.memory.buffer, ea.gene_distribution(), ea.genotype_len()),
         cellDistribution: copyFloatArray(wasm_bg.memory.buffer, ea.cell_distribution(), ea.phenotype_len()),
+        numObjectives,
+        objectives: objectives.map(objective => ({
+            name: ea.objective_name(objective),
+            best: ea.objective_best(objective),
+            avg: ea.objective_avg(objective),
+        })),
+        paretoFront: copyFloatArray(wasm_bg.memory.buffer, ea.pareto_front(), ea.pareto_front_len()),
     }
 }